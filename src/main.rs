@@ -1,69 +1,209 @@
 use std::{
-    io,
-    ops::Add,
-    thread,
+    error::Error,
+    sync::Arc,
     time::Duration,
 };
 
+use clap::Parser;
 use config::Config;
 use cpal::SampleRate;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use quanta::Instant;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
-use rodio::{OutputStream, Sink};
+use rodio::Sink;
+use rodio::buffer::SamplesBuffer;
 use rodio::source::{WhiteNoise, Source};
 use tokio::sync::mpsc;
 
 mod scavnet;
-use scavnet::director::Director;
-use scavnet::interface::MainInterface;
+use scavnet::analysis::LibraryAnalysis;
+use scavnet::archive::SessionArchive;
+use scavnet::audio::core::{null_output, open_output, AudioOutput, OUTPUT_NULL};
+use scavnet::audio::device::list_output_devices;
+use scavnet::audio::rewind::RewindBuffer;
+use scavnet::audio::spectrum::LiveSpectrum;
+use scavnet::bands::{SpectrumBands, SpectrumDisplay};
+use scavnet::cli::{Cli, Command, ConfigArgs, RunArgs};
+use scavnet::commands::{analyze, render, simulate, validate};
+use scavnet::director::{BuildRequest, Director};
+use scavnet::fft::FFTFrameStepper;
+use scavnet::history::HistoryEntry;
+use scavnet::interface::{volume as output_volume, MainInterface, PlaybackRequest, ScannerRequest};
+use scavnet::interfaces::plain::PlainEvent;
+use scavnet::keys::init_key_map;
+use scavnet::layout::init_layouts;
 use scavnet::scanner::Scanner;
-use scavnet::settings::{init_settings, get_volumes};
+use scavnet::settings::{init_settings, get_archive_settings, get_audio_output, get_audio_output_device, get_rewind_buffer_time, get_volumes, uses_precomputed_spectrum};
+use scavnet::settings::{get_cache_dir, get_key_bindings, get_layout_settings, get_theme_settings, get_web_settings, transmission_data_dir};
 use scavnet::system::System;
-use scavnet::transmission::core::Transmission;
+use scavnet::theme::init_themes;
+use scavnet::transmission::core::{Transmission, TransmissionItem};
+use scavnet::transmission::library::LibraryRowKind;
+use scavnet::web::WebServer;
 
 lazy_static! {
-    static ref LAST_KEYEVENT: Mutex<KeyEvent> = Mutex::new(KeyEvent::new(KeyCode::Null, KeyModifiers::NONE));
     static ref SETTINGS: Mutex<Config> = Mutex::new(Config::builder().build().unwrap());
 }
 
+struct Audio {
+    output: Box<dyn AudioOutput>,
+    rewind: Option<Arc<RewindBuffer>>,
+    spectrum: Option<Arc<LiveSpectrum>>,
+}
+
 #[tokio::main]
 async fn main() {
-    let rng = StdRng::from_entropy();
+    let cli = Cli::parse();
+    if let Err(e) = dispatch(cli.command.unwrap_or(Command::Run(RunArgs::default()))).await {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn dispatch(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Run(args) => run(args).await,
+        Command::Validate(config) => {
+            load_config(&config)?;
+            validate().await
+        }
+        Command::Analyze(args) => {
+            load_config(&args.config)?;
+            analyze(args.prune)
+        }
+        Command::Render(args) => {
+            load_config(&args.config)?;
+            render(&args.set, &args.conversation, &args.output, &mut seeded_rng(args.seed)).await
+        }
+        Command::Simulate(args) => {
+            load_config(&args.config)?;
+            simulate(args.duration, &mut seeded_rng(args.seed)).await
+        }
+        Command::Devices => {
+            for device in list_output_devices()? {
+                println!("{}", device);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn load_config(config: &ConfigArgs) -> Result<(), Box<dyn Error>> {
+    init_settings(&config.config, config.data_dir.clone(), false)?;
+    Ok(())
+}
+
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    seed.map(StdRng::seed_from_u64).unwrap_or_else(StdRng::from_entropy)
+}
+
+async fn run(args: RunArgs) -> Result<(), Box<dyn Error>> {
+    let rng = seeded_rng(args.seed);
 
-    let (screen_redraw_rate, debug) = init_settings();
+    let (screen_redraw_rate, debug) = init_settings(&args.config.config, args.config.data_dir.clone(), args.debug)?;
     let frame_time = Duration::from_secs_f64(1.0 / screen_redraw_rate as f64);
     let mut last_frame_time = Instant::now();
 
-    let (signal_tx, signal_rx): (mpsc::Sender<()>, mpsc::Receiver<()>) = mpsc::channel(16);
+    let (signal_tx, signal_rx): (mpsc::Sender<BuildRequest>, mpsc::Receiver<BuildRequest>) = mpsc::channel(16);
     let (queue_tx, mut queue_rx): (mpsc::Sender<Transmission>, mpsc::Receiver<Transmission>) = mpsc::channel(16);
 
-    let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-
-    let director_result = Director::new(rng.clone()).await;
-    let mut director = match director_result {
-        Ok(director) => director,
+    let mut system = System::new();
+    let (theme_name, theme_dir) = get_theme_settings();
+    for error in init_themes(std::path::Path::new(&theme_dir), &theme_name) {
+        system.log(error);
+    }
+    for error in init_key_map(&get_key_bindings()) {
+        system.log(error);
+    }
+    let (layout_mode, layout_descriptions) = get_layout_settings();
+    for error in init_layouts(&layout_mode, &layout_descriptions) {
+        system.log(error);
+    }
+    let audio_output_name = match (args.audio_output, args.headless) {
+        (Some(output), _) => output,
+        (None, true) => OUTPUT_NULL.to_string(),
+        (None, false) => get_audio_output(),
+    };
+    let audio_device = args.audio_device.unwrap_or_else(get_audio_output_device);
+    let audio_output = match open_output(&audio_output_name, &audio_device) {
+        Ok(output) => output,
         Err(e) => {
-            eprintln!("Error initializing Director: {}", e);
-            return;
+            system.log(format!("Audio output '{}' unavailable ({}), discarding audio.", audio_output_name, e));
+            null_output()?
         }
     };
 
+    let mut rewind = None;
+    match get_rewind_buffer_time() {
+        Ok(0) => {}
+        Ok(rewind_buffer_time) => {
+            let rewind_buffer = Arc::new(RewindBuffer::new(rewind_buffer_time, &audio_output.taps()));
+            audio_output.taps().add(rewind_buffer.clone());
+            rewind = Some(rewind_buffer);
+        }
+        Err(e) => system.log(format!("Rewind unavailable ({}).", e)),
+    }
+
+    let spectrum_bands = SpectrumBands::from_settings()?;
+    let mut spectrum = None;
+    if !uses_precomputed_spectrum() {
+        let live_spectrum = Arc::new(LiveSpectrum::new(spectrum_bands.clone()));
+        audio_output.taps().add(live_spectrum.clone());
+        spectrum = Some(live_spectrum);
+    }
+
+    let mut archive = None;
+    match get_archive_settings() {
+        Ok((true, archive_dir, archive_max_entries, archive_max_age_days)) => {
+            match SessionArchive::new(archive_dir.clone(), archive_max_entries, archive_max_age_days, &audio_output.taps()) {
+                Ok(session_archive) => archive = Some(session_archive),
+                Err(e) => system.log(format!("Archive '{}' unavailable ({}), not archiving.", archive_dir, e)),
+            }
+        }
+        Ok(_) => {}
+        Err(e) => system.log(format!("Archive unavailable ({}), not archiving.", e)),
+    }
+
+    let mut director = Director::new(rng.clone()).await
+        .map_err(|e| format!("Error initializing Director: {}", e))?;
+
+    let mut library_analysis = None;
+    if uses_precomputed_spectrum() {
+        let analysis = LibraryAnalysis::start(transmission_data_dir().into(), get_cache_dir().into());
+        library_analysis = Some(analysis);
+    }
+
     tokio::spawn(transmission_builder(signal_rx, queue_tx, director.clone()));
 
-    let mut scanner = Scanner::new(director.get_networks().clone());
+    let mut scanner = Scanner::new(director.get_networks().clone(), spectrum_bands, SpectrumDisplay::from_settings());
+    if let Some(rewind) = &rewind {
+        scanner.attach_rewind_buffer(rewind.clone());
+    }
     let mut cycles: u128 = 0;
-    let mut system = System::new();
-    let mut interface = MainInterface::new();
+    let mut library_was_open = false;
+    let mut builder_busy = false;
+    let mut interface = MainInterface::new(audio_output.uses_stdout(), args.plain || args.headless);
+    match get_web_settings() {
+        Ok((true, web_bind, web_port)) => match WebServer::start(&format!("{}:{}", web_bind, web_port)).await {
+            Ok(web) => {
+                system.log(format!("Web front-end at http://{}/", web.address()));
+                interface.attach_web_server(web);
+            }
+            Err(e) => system.log(format!("Web front-end unavailable on {}:{} ({}).", web_bind, web_port, e)),
+        },
+        Ok(_) => {}
+        Err(e) => system.log(format!("Web front-end unavailable ({}).", e)),
+    }
+    let audio = Audio { output: audio_output, rewind, spectrum };
 
     if debug {
         system.debug_log("Debug mode enabled.".to_string());
     }
 
     scanner.start();
+    system.log(format!("Audio output: {}", audio.output.name()));
     system.log("Connecting to Antenna...".to_string());
     interface.draw(&scanner, &system);
 
@@ -71,40 +211,83 @@ async fn main() {
     system.log("Ready!".to_string());
 
     while !interface.get_exit() {
-        handle_key_events_thread();
-        interface.react_to_key_events();
-
-        if let Some(current_freq) = scanner.next_freq() {
+        interface.react_to_input_events();
+        cycles = handle_playback_request(&audio, None, &mut scanner, &mut interface, &mut system, cycles, screen_redraw_rate);
+
+        handle_cue_request(&interface, &signal_tx, &scanner, &mut system);
+        handle_scanner_request(&interface, &mut scanner, &mut system);
+        handle_replay_request(&interface, &signal_tx, &scanner, &mut system);
+
+        // Cued transmissions are tuned to straight away, even while held; otherwise carry on
+        // scanning.
+        let cued_freq = director.queue.transmissions.iter().find(|trans| trans.cued).map(|trans| trans.frequency);
+        let next_freq = match cued_freq {
+            Some(frequency) if scanner.is_scanning() || scanner.is_held() => {
+                scanner.tune(frequency);
+                Some(frequency)
+            }
+            _ => scanner.next_freq(),
+        };
+        if let Some(current_freq) = next_freq {
             director.queue.transmissions.retain(|trans| {
                 if trans.frequency == current_freq {
                     system.log(format!("Signal Detected! Frequency: {}.", scanner.cur_freq_display()));
+                    interface.announce(PlainEvent::SignalDetected {
+                        frequency: scanner.cur_freq_display(),
+                        network: scanner.cur_network_name(),
+                        transmission: trans.id.clone(),
+                    });
                     let (transmission_volume, hiss_volume) = get_volumes();
-                    scanner.pause_for_playback();
 
                     // Play the transmission.
-                    let sink = Sink::try_new(&stream_handle).unwrap();
+                    let sink = match audio.output.new_sink() {
+                        Ok(sink) => sink,
+                        Err(e) => {
+                            system.log(format!("Could not play transmission {} ({}), dropping it.", trans.id, e));
+                            return false;
+                        }
+                    };
+                    scanner.pause_for_playback();
+                    system.history_mut().add(HistoryEntry::new(trans, scanner.cur_freq_display(), scanner.cur_network_name()));
+                    if let Some(archive) = archive.as_mut() {
+                        archive.start(trans, scanner.cur_freq_display(), scanner.cur_network_name());
+                    }
 
-                    let mut items_iter = trans.items.iter();
-                    cycles = play_hiss(trans.hiss_preroll, hiss_volume, &sink, &mut scanner, &mut interface, &mut system, cycles, screen_redraw_rate);
+                    cycles = play_hiss(trans.hiss_preroll, hiss_volume, &audio, &sink, &mut scanner, &mut interface, &mut system, cycles, screen_redraw_rate);
 
-                    while let Some(item) = items_iter.next() {
+                    for item in trans.items.iter() {
+                        if scanner.is_skipping() {
+                            break;
+                        }
                         system.debug_log(format!("Playing transmission item: {}", item.id));
+                        interface.announce(PlainEvent::ItemPlaying {
+                            frequency: scanner.cur_freq_display(),
+                            item: item.id.clone(),
+                            caption: item.caption.clone(),
+                        });
 
                         let cursor = std::io::Cursor::new(item.file_bytes.clone());
                         let source = rodio::Decoder::new(cursor).unwrap();
 
-                        sink.set_volume(transmission_volume);
+                        sink.set_volume(transmission_volume * output_volume());
                         sink.append(source);
 
-                        cycles = update_fft_data_during_playback(&sink, item.duration, &item.fft_data, &mut scanner, &mut system, cycles, &mut interface, screen_redraw_rate);
+                        cycles = update_fft_data_during_playback(&audio, &sink, item, transmission_volume, &mut scanner, &mut system, cycles, &mut interface, screen_redraw_rate);
+                        scanner.set_caption(None);
 
-                        if item.sleep_after > 0.0 {
-                            cycles = play_hiss(item.sleep_after, hiss_volume, &sink, &mut scanner, &mut interface, &mut system, cycles, screen_redraw_rate);
+                        if item.sleep_after > 0.0 && !scanner.is_skipping() {
+                            cycles = play_hiss(item.sleep_after, hiss_volume, &audio, &sink, &mut scanner, &mut interface, &mut system, cycles, screen_redraw_rate);
                         }
                     }
 
-                    cycles = play_hiss(trans.hiss_postroll, hiss_volume, &sink, &mut scanner, &mut interface, &mut system, cycles, screen_redraw_rate);
+                    if !scanner.is_skipping() {
+                        cycles = play_hiss(trans.hiss_postroll, hiss_volume, &audio, &sink, &mut scanner, &mut interface, &mut system, cycles, screen_redraw_rate);
+                    }
+                    if let Some(archive) = archive.as_mut() {
+                        archive.stop();
+                    }
                     system.log(format!("Signal Lost on frequency {}", scanner.cur_freq_display()));
+                    interface.announce(PlainEvent::SignalLost { frequency: scanner.cur_freq_display() });
                     scanner.resume_after_playback();
                     false
                 } else {
@@ -115,19 +298,56 @@ async fn main() {
 
         if last_frame_time.elapsed() >= frame_time {
 
+            // The library rows are read from the sets once when the browser opens, and again
+            // once a cue has been built and marked played.
+            let library_open = interface.is_library_open();
+            let mut refresh_library = library_open && !library_was_open;
+            library_was_open = library_open;
             while let Ok(data) = queue_rx.try_recv() {
                 system.debug_log(format!("Received new transmission: {}", data.id));
+                refresh_library |= library_open && data.cued;
                 director.queue.add(data);
             }
+            scanner.set_queued_frequencies(director.queue.transmissions.iter().map(|trans| trans.frequency).collect());
+            if debug {
+                system.set_director_inspection(Some(director.inspect()));
+            }
+            if refresh_library {
+                system.set_library_rows(director.library_rows());
+            }
 
             scanner.simulate_noise();
 
+            if let Some(analysis) = &library_analysis {
+                report_analysis_progress(analysis, &mut system);
+            }
+            if let Some(archive) = &archive {
+                for error in archive.take_errors() {
+                    system.log(error);
+                }
+            }
+            for error in audio.output.take_errors() {
+                system.log(error);
+            }
+
             if director.needs_queueing() {
-                system.debug_log("Queueing new transmission.".to_string());
-                if let Err(_) = signal_tx.try_send(()) {
-                    panic!("Failed to signal builder.");
+                match signal_tx.try_send(BuildRequest::Random) {
+                    Ok(()) => {
+                        system.debug_log("Queueing new transmission.".to_string());
+                        director.set_next_queue_time();
+                        builder_busy = false;
+                    }
+                    // Operator requests can fill the channel; try again on the next tick.
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        if !builder_busy {
+                            system.debug_log("Transmission builder busy, queueing later.".to_string());
+                            builder_busy = true;
+                        }
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => {
+                        panic!("Failed to signal builder.");
+                    }
                 }
-                director.set_next_queue_time();
             }
 
             interface.draw(&scanner, &system);
@@ -140,32 +360,89 @@ async fn main() {
     interface.cleanup();
 
     // Remove when releasing.
-    println!("Cycles: {}", cycles);
+    if !audio.output.uses_stdout() && !interface.is_plain() {
+        println!("Cycles: {}", cycles);
+    }
+    Ok(())
+}
+
+fn report_analysis_progress(analysis: &LibraryAnalysis, system: &mut System) {
+    for event in analysis.take_events() {
+        system.log(event);
+    }
+    let progress = analysis.progress();
+    if progress.finished {
+        system.set_progress(None);
+    } else {
+        system.set_progress(Some(progress.summary()));
+    }
+}
+
+
+// Asks the builder for the conversation picked in the library browser.
+fn handle_cue_request(interface: &MainInterface, signal_tx: &mpsc::Sender<BuildRequest>, scanner: &Scanner, system: &mut System) {
+    let Some(cue) = interface.take_cue_request() else {
+        return;
+    };
+    let Some(LibraryRowKind::Conversation { set_path, id, .. }) = system.get_library_rows().get(cue.row).map(|row| row.kind.clone()) else {
+        system.log("Select a conversation to cue.".to_string());
+        return;
+    };
+
+    let frequency = cue.on_current_frequency.then(|| scanner.cur_freq());
+    system.log(format!("Cueing conversation {}.", id));
+    let request = BuildRequest::Cue { set_path, conversation: id, frequency };
+    if signal_tx.try_send(request).is_err() {
+        system.log("Transmission builder busy, try again.".to_string());
+    }
 }
 
-fn handle_key_events_thread() {
-    thread::spawn(|| {
-        let _ = handle_key_events();
-    });
+// Builds a transmission clicked in the transcript again, to play on the current frequency.
+fn handle_replay_request(interface: &MainInterface, signal_tx: &mpsc::Sender<BuildRequest>, scanner: &Scanner, system: &mut System) {
+    let Some(index) = interface.take_replay_request() else {
+        return;
+    };
+    let Some(entry) = system.history().get(index) else {
+        return;
+    };
+    let Some(set_path) = entry.set_path.clone() else {
+        system.log(format!("Transmission {} can't be replayed.", entry.conversation));
+        return;
+    };
+
+    let conversation = entry.conversation.clone();
+    system.log(format!("Replaying transmission {}.", conversation));
+    let request = BuildRequest::Cue { set_path, conversation, frequency: Some(scanner.cur_freq()) };
+    if signal_tx.try_send(request).is_err() {
+        system.log("Transmission builder busy, try again.".to_string());
+    }
 }
 
-fn handle_key_events() -> io::Result<bool> {
-    if event::poll(std::time::Duration::from_millis(20))? {
-        if let Event::Key(key) = event::read()? {
-            *LAST_KEYEVENT.lock() = key.clone();
+fn handle_scanner_request(interface: &MainInterface, scanner: &mut Scanner, system: &mut System) {
+    match interface.take_scanner_request() {
+        Some(ScannerRequest::Hold) => {
+            scanner.toggle_hold();
+            system.log(format!("{} on {}.", scanner.status(), scanner.cur_freq_display()));
         }
+        Some(ScannerRequest::Tune(steps)) => scanner.tune_step(steps),
+        Some(ScannerRequest::JumpToNetwork(index)) => {
+            scanner.jump_to_network(index);
+            system.log(format!("Holding on {} at {}.", scanner.cur_network_name(), scanner.cur_freq_display()));
+        }
+        None => {}
     }
-    Ok(false)
 }
 
 async fn transmission_builder(
-    mut signal_rx: mpsc::Receiver<()>,
+    mut signal_rx: mpsc::Receiver<BuildRequest>,
     queue_tx: mpsc::Sender<Transmission>,
-    director: Director,
+    mut director: Director,
 ) {
 
-    while let Some(_) = signal_rx.recv().await {
-        let data = director.get_new_transmission().await;
+    while let Some(request) = signal_rx.recv().await {
+        let Some(data) = director.get_new_transmission(request).await else {
+            continue;
+        };
         if queue_tx.send(data).await.is_err() {
             println!("Main loop dropped, exiting background task.");
             break;
@@ -175,58 +452,132 @@ async fn transmission_builder(
 }
 
 // Move these to a separate modules - scanner?
-fn update_fft_data_during_playback(sink: &Sink, file_duration: f32, fft_data: &Vec<Vec<f32>>, scanner: &mut Scanner, system: &mut System, cycles: u128, interface: &mut MainInterface, screen_redraw_rate: u128) -> u128{
-    let start_time = Instant::now();
-    let count_fft_data_points = fft_data.len();
-    let duration_per_fft_data_point = file_duration / count_fft_data_points as f32;
-    let mut next_fft_update_time = Some(start_time.add(Duration::from_secs_f32(duration_per_fft_data_point)));
-    let mut fft_data_index = 0;
+#[allow(clippy::too_many_arguments)]
+fn update_fft_data_during_playback(audio: &Audio, sink: &Sink, item: &TransmissionItem, volume: f32, scanner: &mut Scanner, system: &mut System, cycles: u128, interface: &mut MainInterface, screen_redraw_rate: u128) -> u128{
+    let mut fft_frames = FFTFrameStepper::new(&item.fft_data, item.duration, Instant::now());
     let mut cycles = cycles;
 
     while !sink.empty() {
-        handle_key_events_thread();
-        interface.react_to_key_events();
+        interface.react_to_input_events();
+        cycles = handle_playback_request(audio, Some(sink), scanner, interface, system, cycles, screen_redraw_rate);
 
-        if cycles % screen_redraw_rate == 0 {
+        if cycles.is_multiple_of(screen_redraw_rate) {
             let now = Instant::now();
-            if let Some(next_update_time) = next_fft_update_time {
-                if now >= next_update_time {
-                    scanner.update_fft_data(fft_data[fft_data_index].clone());
-                    fft_data_index += 1;
-
-                    if fft_data_index <= count_fft_data_points {
-                        next_fft_update_time = Some(now.add(Duration::from_secs_f32(duration_per_fft_data_point)));
-                    } else {
-                        next_fft_update_time = None;
-                    }
+            sink.set_volume(volume * output_volume());
+            scanner.set_caption(item.caption_at(sink.get_pos().as_secs_f32()).map(str::to_string));
+            let live_spectrum = update_live_spectrum(audio, scanner);
+            if !live_spectrum {
+                if let Some(fft_data_point) = fft_frames.next_frame(now) {
+                    scanner.update_fft_data(fft_data_point.clone());
                 }
             }
-            interface.draw(&scanner, &system);
+            interface.draw(scanner, system);
         }
         cycles += 1;
     }
 
-    return cycles;
+    cycles
 }
 
-fn play_hiss(hiss_time: f32, hiss_volume: f32, sink: &Sink, scanner: &mut Scanner, interface: &mut MainInterface, system: &mut System, cycles: u128, screen_redraw_rate: u128) -> u128 {
+#[allow(clippy::too_many_arguments)]
+fn play_hiss(hiss_time: f32, hiss_volume: f32, audio: &Audio, sink: &Sink, scanner: &mut Scanner, interface: &mut MainInterface, system: &mut System, cycles: u128, screen_redraw_rate: u128) -> u128 {
     let mut local_cycles = cycles;
     let white_noise_source = WhiteNoise::new(SampleRate(44100));
     let hiss_millisecs =  (hiss_time * 1000.0) as u64;
     let white_noise = white_noise_source.take_duration(Duration::from_millis(hiss_millisecs));
-    sink.set_volume(hiss_volume);
+    sink.set_volume(hiss_volume * output_volume());
 
     system.debug_log(format!("Generating hiss for {} seconds.", hiss_time));
     sink.append(white_noise);
 
     while !sink.empty() {
-        handle_key_events_thread();
-        if local_cycles % screen_redraw_rate == 0 {
-            scanner.simulate_hiss_noise();
-            interface.draw(&scanner, &system);
+        interface.react_to_input_events();
+        local_cycles = handle_playback_request(audio, Some(sink), scanner, interface, system, local_cycles, screen_redraw_rate);
+        if local_cycles.is_multiple_of(screen_redraw_rate) {
+            sink.set_volume(hiss_volume * output_volume());
+            if !update_live_spectrum(audio, scanner) {
+                scanner.simulate_hiss_noise();
+            }
+            interface.draw(scanner, system);
+        }
+        local_cycles += 1;
+    }
+
+    local_cycles
+}
+
+// Shows the newest live spectrum frame, if the live spectrum is in use. Returns whether it is.
+fn update_live_spectrum(audio: &Audio, scanner: &mut Scanner) -> bool {
+    let Some(spectrum) = &audio.spectrum else {
+        return false;
+    };
+    if let Some(frame) = spectrum.take_frame() {
+        scanner.update_fft_data(frame);
+    }
+    true
+}
+
+fn handle_playback_request(audio: &Audio, live_sink: Option<&Sink>, scanner: &mut Scanner, interface: &mut MainInterface, system: &mut System, cycles: u128, screen_redraw_rate: u128) -> u128 {
+    match interface.take_playback_request() {
+        Some(PlaybackRequest::Rewind(secs)) => replay(secs, audio, live_sink, scanner, interface, system, cycles, screen_redraw_rate),
+        Some(PlaybackRequest::Skip) => {
+            if let Some(live_sink) = live_sink {
+                live_sink.stop();
+                scanner.skip_transmission();
+                system.log("Transmission skipped.".to_string());
+            }
+            cycles
+        }
+        _ => cycles,
+    }
+}
+
+// Pauses live audio and plays the last `secs` seconds from the rewind buffer, along with the
+// spectrum frames that were displayed at the time.
+#[allow(clippy::too_many_arguments)]
+fn replay(secs: f32, audio: &Audio, live_sink: Option<&Sink>, scanner: &mut Scanner, interface: &mut MainInterface, system: &mut System, cycles: u128, screen_redraw_rate: u128) -> u128 {
+    let Some(replay) = audio.rewind.as_ref().and_then(|rewind| rewind.replay(secs)) else {
+        return cycles;
+    };
+    let Ok(sink) = audio.output.new_sink() else {
+        return cycles;
+    };
+    let mut local_cycles = cycles;
+    let taps = audio.output.taps();
+
+    if let Some(live_sink) = live_sink {
+        live_sink.pause();
+    }
+    taps.set_suspended(true);
+    scanner.begin_replay(secs);
+    system.log(format!("Replaying the last {} seconds.", secs));
+
+    sink.append(SamplesBuffer::new(replay.channels, replay.sample_rate, replay.samples));
+    let mut fft_frames = replay.fft_frames.into_iter().peekable();
+
+    while !sink.empty() {
+        interface.react_to_input_events();
+        if interface.take_playback_request() == Some(PlaybackRequest::Live) {
+            sink.stop();
+            break;
+        }
+
+        if local_cycles.is_multiple_of(screen_redraw_rate) {
+            let position = sink.get_pos();
+            while let Some((_, fft_frame)) = fft_frames.next_if(|(offset, _)| *offset <= position) {
+                scanner.replay_fft_data(fft_frame);
+            }
+            interface.draw(scanner, system);
         }
         local_cycles += 1;
     }
 
+    taps.set_suspended(false);
+    scanner.end_replay();
+    if let Some(live_sink) = live_sink {
+        live_sink.play();
+    }
+    system.log("Back to live.".to_string());
+
     local_cycles
 }
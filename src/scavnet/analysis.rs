@@ -0,0 +1,137 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+
+use parking_lot::Mutex;
+use walkdir::WalkDir;
+
+use crate::scavnet::fft::{read_fft_data, write_fft_data};
+
+#[derive(Clone, Debug, Default)]
+pub struct AnalysisProgress {
+    pub total: usize,
+    pub analysed: usize,
+    pub cached: usize,
+    pub failed: Vec<(String, String)>,
+    pub finished: bool,
+}
+
+impl AnalysisProgress {
+    pub fn processed(&self) -> usize {
+        self.analysed + self.cached + self.failed.len()
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "Library analysis: {}/{} files ({} analysed, {} cached, {} failed)",
+            self.processed(),
+            self.total,
+            self.analysed,
+            self.cached,
+            self.failed.len()
+        )
+    }
+}
+
+/// Analyses every WAV file in the transmission library on a background thread, skipping files
+/// whose analysis is already cached. Scanning can start straight away; transmissions built
+/// before their files are reached are analysed on demand.
+pub struct LibraryAnalysis {
+    progress: Arc<Mutex<AnalysisProgress>>,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl LibraryAnalysis {
+    pub fn start(library_dir: PathBuf, cache_dir: PathBuf) -> Self {
+        let progress = Arc::new(Mutex::new(AnalysisProgress::default()));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let worker_progress = progress.clone();
+        let worker_events = events.clone();
+
+        thread::spawn(move || {
+            let (files, walk_errors) = library_wav_files(&library_dir);
+            {
+                let mut progress = worker_progress.lock();
+                progress.total = files.len() + walk_errors.len();
+                progress.failed.extend(walk_errors);
+            }
+
+            for file in files {
+                let file_path = file.to_string_lossy().to_string();
+                if read_fft_data(&file_path, &cache_dir).is_ok() {
+                    worker_progress.lock().cached += 1;
+                    continue;
+                }
+                match write_fft_data(&file_path, &cache_dir) {
+                    Ok(_) => worker_progress.lock().analysed += 1,
+                    Err(e) => worker_progress.lock().failed.push((file_path, e.to_string())),
+                }
+            }
+
+            let mut progress = worker_progress.lock();
+            progress.finished = true;
+            let mut events = worker_events.lock();
+            events.push(progress.summary());
+            for (file_path, error) in &progress.failed {
+                events.push(format!("Analysis failed: {}: {}", file_path, error));
+            }
+        });
+
+        Self { progress, events }
+    }
+
+    pub fn progress(&self) -> AnalysisProgress {
+        self.progress.lock().clone()
+    }
+
+    /// Messages for the SYSTEM log since the last call.
+    pub fn take_events(&self) -> Vec<String> {
+        std::mem::take(&mut *self.events.lock())
+    }
+}
+
+/// Every WAV file under `dir`, and the paths that couldn't be read along with why.
+pub fn library_wav_files(dir: &Path) -> (Vec<PathBuf>, Vec<(String, String)>) {
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        match entry {
+            Ok(entry) => {
+                let path = entry.path();
+                if entry.file_type().is_file() && path.extension().is_some_and(|extension| extension == "wav") {
+                    files.push(path.to_path_buf());
+                }
+            }
+            Err(e) => {
+                let path = e.path().unwrap_or(dir).to_string_lossy().to_string();
+                errors.push((path, e.to_string()));
+            }
+        }
+    }
+    (files, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scavnet::testing::test_dir;
+
+    use std::fs;
+
+    #[test]
+    fn test_library_wav_files() {
+        let scratch = test_dir();
+        let dir = scratch.path();
+        fs::create_dir_all(dir.join("set")).unwrap();
+        fs::write(dir.join("set/one.wav"), "").unwrap();
+        fs::write(dir.join("set/sets.yaml"), "").unwrap();
+        fs::write(dir.join("README"), "").unwrap();
+
+        let (files, errors) = library_wav_files(dir);
+        let missing = library_wav_files(&dir.join("missing"));
+
+        assert_eq!(files, [dir.join("set/one.wav")]);
+        assert!(errors.is_empty());
+        assert_eq!(missing.1.len(), 1);
+    }
+}
@@ -0,0 +1,209 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::scavnet::audio::tap::{AudioTaps, SampleTap};
+use crate::scavnet::audio::wav::write_wav_file;
+use crate::scavnet::transmission::core::Transmission;
+
+const RECORD_EXTENSION: &str = "yaml";
+const AUDIO_EXTENSION: &str = "wav";
+
+/// Metadata saved alongside each archived transmission.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ArchiveRecord {
+    pub conversation: String,
+    pub received_at: String,
+    pub duration: f32,
+    pub frequency: u32,
+    pub frequency_display: String,
+    pub network: String,
+    pub items: Vec<String>,
+    pub captions: Vec<String>,
+    pub audio_file: String,
+}
+
+/// Collects the output mix while a transmission is being received.
+struct Recorder {
+    samples: Mutex<Option<Vec<f32>>>,
+}
+
+impl SampleTap for Recorder {
+    fn write_samples(&self, samples: &[f32], _channels: u16, _sample_rate: u32) {
+        if let Some(recording) = self.samples.lock().as_mut() {
+            recording.extend_from_slice(samples);
+        }
+    }
+}
+
+/// The session tape: saves every received transmission, hiss included, exactly as it was
+/// played, as a WAV file with a YAML record next to it.
+pub struct SessionArchive {
+    dir: PathBuf,
+    max_entries: usize,
+    max_age: Duration,
+    channels: u16,
+    sample_rate: u32,
+    recorder: Arc<Recorder>,
+    current: Option<ArchiveRecord>,
+    // Transmissions that couldn't be written, reported by the writing threads.
+    errors_tx: Sender<String>,
+    errors_rx: Receiver<String>,
+}
+
+impl SessionArchive {
+    /// Keeps at most `max_entries` transmissions, none older than `max_age_days`. A limit of 0
+    /// disables it.
+    pub fn new(dir: String, max_entries: usize, max_age_days: u64, taps: &AudioTaps) -> Result<Self, Box<dyn Error>> {
+        let dir = PathBuf::from(dir);
+        fs::create_dir_all(&dir)?;
+        let recorder = Arc::new(Recorder { samples: Mutex::new(None) });
+        taps.add(recorder.clone());
+        let (errors_tx, errors_rx) = mpsc::channel();
+
+        Ok(Self {
+            dir,
+            max_entries,
+            max_age: Duration::from_secs(max_age_days * 24 * 60 * 60),
+            channels: taps.channels(),
+            sample_rate: taps.sample_rate(),
+            recorder,
+            current: None,
+            errors_tx,
+            errors_rx,
+        })
+    }
+
+    pub fn start(&mut self, transmission: &Transmission, frequency_display: String, network: String) {
+        let now = chrono::Local::now();
+        let audio_file = format!("{}-{}.{}", now.format("%Y%m%d-%H%M%S"), transmission.frequency, AUDIO_EXTENSION);
+        self.current = Some(ArchiveRecord {
+            conversation: transmission.id.clone(),
+            received_at: now.to_rfc3339(),
+            duration: 0.0,
+            frequency: transmission.frequency,
+            frequency_display,
+            network,
+            items: transmission.items.iter().map(|item| item.id.clone()).collect(),
+            captions: transmission.items.iter().map(|item| item.caption.clone()).collect(),
+            audio_file,
+        });
+        *self.recorder.samples.lock() = Some(Vec::new());
+    }
+
+    /// Stops recording and writes the transmission out in the background.
+    pub fn stop(&mut self) {
+        let samples = self.recorder.samples.lock().take();
+        let (Some(mut record), Some(samples)) = (self.current.take(), samples) else {
+            return;
+        };
+
+        record.duration = samples.len() as f32 / (self.channels as f32 * self.sample_rate as f32);
+        let dir = self.dir.clone();
+        let (channels, sample_rate) = (self.channels, self.sample_rate);
+        let (max_entries, max_age) = (self.max_entries, self.max_age);
+        let errors_tx = self.errors_tx.clone();
+
+        thread::spawn(move || {
+            if let Err(e) = write_entry(&dir, &record, &samples, channels, sample_rate) {
+                // Only fails once the archive is gone, when there's no log to report to.
+                let _ = errors_tx.send(format!("Could not archive transmission {} ({}).", record.conversation, e));
+            }
+            prune_archive(&dir, max_entries, max_age);
+        });
+    }
+
+    /// Messages for the SYSTEM log about transmissions that couldn't be archived since the last
+    /// call.
+    pub fn take_errors(&self) -> Vec<String> {
+        self.errors_rx.try_iter().collect()
+    }
+}
+
+// Writes a transmission's audio, then its record.
+fn write_entry(dir: &Path, record: &ArchiveRecord, samples: &[f32], channels: u16, sample_rate: u32) -> Result<(), Box<dyn Error>> {
+    let audio_path = dir.join(&record.audio_file);
+    write_wav_file(&audio_path, samples, channels, sample_rate)?;
+    fs::write(audio_path.with_extension(RECORD_EXTENSION), serde_yaml::to_string(record)?)?;
+    Ok(())
+}
+
+/// Removes the oldest archived transmissions beyond `max_entries`, and any older than `max_age`.
+pub fn prune_archive(dir: &Path, max_entries: usize, max_age: Duration) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    // File names start with the time received, so sorting by name sorts by age.
+    let mut records: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == RECORD_EXTENSION))
+        .collect();
+    records.sort();
+
+    let excess = if max_entries > 0 { records.len().saturating_sub(max_entries) } else { 0 };
+    for (index, record_path) in records.iter().enumerate() {
+        let expired = !max_age.is_zero() && fs::metadata(record_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > max_age);
+
+        if index < excess || expired {
+            let _ = fs::remove_file(record_path.with_extension(AUDIO_EXTENSION));
+            let _ = fs::remove_file(record_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scavnet::testing::test_dir;
+
+    #[test]
+    fn test_archive_reports_write_errors() {
+        let scratch = test_dir();
+        let dir = scratch.path().join("archive");
+        let taps = AudioTaps::new(1, 8000);
+        let mut archive = SessionArchive::new(dir.to_string_lossy().to_string(), 0, 0, &taps).unwrap();
+        fs::remove_dir(&dir).unwrap();
+
+        archive.start(&Transmission::new(1000), "100.0".to_string(), "Test".to_string());
+        taps.write_samples(&[0.0; 16]);
+        archive.stop();
+
+        let error = archive.errors_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(error.starts_with("Could not archive transmission"), "{}", error);
+    }
+
+    #[test]
+    fn test_prune_archive_max_entries() {
+        let scratch = test_dir();
+        let dir = scratch.path();
+        for name in ["20250101-000000-1", "20250102-000000-1", "20250103-000000-1"] {
+            fs::write(dir.join(format!("{}.yaml", name)), "").unwrap();
+            fs::write(dir.join(format!("{}.wav", name)), "").unwrap();
+        }
+
+        prune_archive(dir, 2, Duration::ZERO);
+
+        let mut remaining: Vec<String> = fs::read_dir(dir).unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        remaining.sort();
+        assert_eq!(remaining, [
+            "20250102-000000-1.wav",
+            "20250102-000000-1.yaml",
+            "20250103-000000-1.wav",
+            "20250103-000000-1.yaml",
+        ]);
+    }
+}
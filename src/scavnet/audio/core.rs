@@ -0,0 +1,61 @@
+use std::error::Error;
+
+use rodio::Sink;
+
+use crate::scavnet::audio::device::{DeviceOutput, OUTPUT_DEVICE_NULL};
+use crate::scavnet::audio::null::NullWriter;
+use crate::scavnet::audio::pcm::PcmWriter;
+use crate::scavnet::audio::pump::PumpedOutput;
+use crate::scavnet::audio::tap::AudioTaps;
+use crate::scavnet::audio::wav::RollingWavWriter;
+use crate::scavnet::settings::{get_audio_format, get_audio_pcm_path, get_audio_wav_settings};
+
+pub const OUTPUT_DEVICE: &str = "device";
+pub const OUTPUT_NULL: &str = "null";
+pub const OUTPUT_WAV: &str = "wav";
+pub const OUTPUT_PCM: &str = "pcm";
+
+/// Destination for everything scavnet plays. Each transmission is played through its own
+/// `Sink`, which the output mixes into a single stream.
+pub trait AudioOutput {
+    fn name(&self) -> String;
+
+    fn new_sink(&self) -> Result<Sink, Box<dyn Error>>;
+
+    /// Taps on the final mix, e.g. for recording what was heard.
+    fn taps(&self) -> AudioTaps;
+
+    /// Whether the output writes to stdout, in which case the interface must not.
+    fn uses_stdout(&self) -> bool {
+        false
+    }
+
+    /// Messages for the SYSTEM log about failures since the last call.
+    fn take_errors(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+pub fn null_output() -> Result<Box<dyn AudioOutput>, Box<dyn Error>> {
+    let (sample_rate, channels) = get_audio_format()?;
+    Ok(Box::new(PumpedOutput::new(NullWriter, channels, sample_rate)))
+}
+
+pub fn open_output(output: &str, device_name: &str) -> Result<Box<dyn AudioOutput>, Box<dyn Error>> {
+    let (sample_rate, channels) = get_audio_format()?;
+    match output {
+        OUTPUT_DEVICE if device_name == OUTPUT_DEVICE_NULL => open_output(OUTPUT_NULL, device_name),
+        OUTPUT_DEVICE => Ok(Box::new(DeviceOutput::open(device_name)?)),
+        OUTPUT_NULL => null_output(),
+        OUTPUT_WAV => {
+            let (wav_dir, segment_secs) = get_audio_wav_settings()?;
+            let writer = RollingWavWriter::new(wav_dir, segment_secs, channels, sample_rate)?;
+            Ok(Box::new(PumpedOutput::new(writer, channels, sample_rate)))
+        }
+        OUTPUT_PCM => {
+            let writer = PcmWriter::new(get_audio_pcm_path());
+            Ok(Box::new(PumpedOutput::new(writer, channels, sample_rate)))
+        }
+        _ => Err(format!("Invalid audio output: {}", output).into()),
+    }
+}
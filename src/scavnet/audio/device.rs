@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use rodio::dynamic_mixer::{self, DynamicMixerController};
+use rodio::{OutputStream, OutputStreamHandle, Sink};
+
+use crate::scavnet::audio::core::AudioOutput;
+use crate::scavnet::audio::tap::{AudioTaps, TappedMix};
+
+pub const OUTPUT_DEVICE_DEFAULT: &str = "default";
+pub const OUTPUT_DEVICE_NULL: &str = "null";
+
+const DEVICE_DEFAULT_CHANNELS: u16 = 2;
+const DEVICE_DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+/// Plays through a sound card via rodio.
+pub struct DeviceOutput {
+    name: String,
+    _stream: OutputStream,
+    mixer: Arc<DynamicMixerController<f32>>,
+    taps: AudioTaps,
+}
+
+impl DeviceOutput {
+    const ERROR_DEVICE_NOT_FOUND: &'static str = "Audio output device not found.";
+
+    /// Opens "default" or a device name as reported by `list_output_devices`.
+    pub fn open(device_name: &str) -> Result<Self, Box<dyn Error>> {
+        match device_name {
+            OUTPUT_DEVICE_DEFAULT => Self::default_device(),
+            name => Self::named_device(name),
+        }
+    }
+
+    pub fn default_device() -> Result<Self, Box<dyn Error>> {
+        let (stream, handle) = OutputStream::try_default()?;
+        let device = cpal::default_host().default_output_device();
+        let name = device.as_ref()
+            .and_then(|device| device.name().ok())
+            .unwrap_or(OUTPUT_DEVICE_DEFAULT.to_string());
+        Self::play(name, device.as_ref(), stream, handle)
+    }
+
+    pub fn named_device(device_name: &str) -> Result<Self, Box<dyn Error>> {
+        let device = cpal::default_host()
+            .output_devices()?
+            .find(|device| device.name().map(|name| name == device_name).unwrap_or(false))
+            .ok_or(Self::ERROR_DEVICE_NOT_FOUND)?;
+        let (stream, handle) = OutputStream::try_from_device(&device)?;
+        Self::play(device_name.to_string(), Some(&device), stream, handle)
+    }
+
+    // Mix at the device's own format so the stream needs no further conversion.
+    fn play(name: String, device: Option<&cpal::Device>, stream: OutputStream, handle: OutputStreamHandle) -> Result<Self, Box<dyn Error>> {
+        let (channels, sample_rate) = device
+            .and_then(|device| device.default_output_config().ok())
+            .map(|config| (config.channels(), config.sample_rate().0))
+            .unwrap_or((DEVICE_DEFAULT_CHANNELS, DEVICE_DEFAULT_SAMPLE_RATE));
+        let (mixer, mixer_rx) = dynamic_mixer::mixer::<f32>(channels, sample_rate);
+        let taps = AudioTaps::new(channels, sample_rate);
+        handle.play_raw(TappedMix::new(mixer_rx, taps.clone()))?;
+        Ok(Self { name, _stream: stream, mixer, taps })
+    }
+}
+
+impl AudioOutput for DeviceOutput {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn new_sink(&self) -> Result<Sink, Box<dyn Error>> {
+        let (sink, queue_rx) = Sink::new_idle();
+        self.mixer.add(queue_rx);
+        Ok(sink)
+    }
+
+    fn taps(&self) -> AudioTaps {
+        self.taps.clone()
+    }
+}
+
+pub fn list_output_devices() -> Result<Vec<String>, Box<dyn Error>> {
+    let devices = cpal::default_host()
+        .output_devices()?
+        .filter_map(|device| device.name().ok())
+        .collect();
+    Ok(devices)
+}
@@ -0,0 +1,10 @@
+pub mod core;
+pub mod device;
+pub mod null;
+pub mod pcm;
+pub mod pump;
+pub mod render;
+pub mod rewind;
+pub mod spectrum;
+pub mod tap;
+pub mod wav;
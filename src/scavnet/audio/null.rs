@@ -0,0 +1,16 @@
+use std::io;
+
+use crate::scavnet::audio::pump::SampleWriter;
+
+/// Discards audio, for headless machines and automated tests.
+pub struct NullWriter;
+
+impl SampleWriter for NullWriter {
+    fn name(&self) -> String {
+        "null".to_string()
+    }
+
+    fn write_samples(&mut self, _samples: &[f32]) -> io::Result<()> {
+        Ok(())
+    }
+}
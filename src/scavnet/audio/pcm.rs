@@ -0,0 +1,91 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::scavnet::audio::pump::SampleWriter;
+
+pub const PCM_PATH_STDOUT: &str = "-";
+
+/// Writes raw interleaved signed 16-bit little-endian PCM to stdout ("-"), a file or a named
+/// pipe, e.g. for `ffmpeg -f s16le -ar 44100 -ac 2 -i <path>`.
+pub struct PcmWriter {
+    path: String,
+    output: Option<Box<dyn Write + Send>>,
+}
+
+impl PcmWriter {
+    pub fn new(path: String) -> Self {
+        Self { path, output: None }
+    }
+
+    // Opened on first write: opening a named pipe blocks until a reader connects. A file is
+    // started afresh each run.
+    fn output(&mut self) -> io::Result<&mut Box<dyn Write + Send>> {
+        if self.output.is_none() {
+            let output: Box<dyn Write + Send> = if self.path == PCM_PATH_STDOUT {
+                Box::new(io::stdout())
+            } else {
+                Box::new(BufWriter::new(File::create(&self.path)?))
+            };
+            self.output = Some(output);
+        }
+        Ok(self.output.as_mut().unwrap())
+    }
+}
+
+impl SampleWriter for PcmWriter {
+    fn name(&self) -> String {
+        format!("pcm ({})", self.path)
+    }
+
+    fn write_samples(&mut self, samples: &[f32]) -> io::Result<()> {
+        let bytes: Vec<u8> = samples.iter()
+            .flat_map(|sample| ((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+            .collect();
+        let output = self.output()?;
+        output.write_all(&bytes)?;
+        output.flush()
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self.output.as_mut() {
+            Some(output) => output.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn uses_stdout(&self) -> bool {
+        self.path == PCM_PATH_STDOUT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scavnet::testing::test_dir;
+
+    #[test]
+    fn test_write_pcm_samples() {
+        let scratch = test_dir();
+        let path = scratch.path().join("samples.pcm");
+        let mut writer = PcmWriter::new(path.to_str().unwrap().to_string());
+
+        writer.write_samples(&[0.0, 1.0, -1.0, 2.0]).unwrap();
+        writer.finish().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(bytes, [0, 0, 0xff, 0x7f, 0x01, 0x80, 0xff, 0x7f]);
+    }
+
+    #[test]
+    fn test_pcm_file_truncated_each_run() {
+        let scratch = test_dir();
+        let path = scratch.path().join("samples.pcm");
+        for samples in [[1.0, 1.0], [0.0, 0.0]] {
+            let mut writer = PcmWriter::new(path.to_str().unwrap().to_string());
+            writer.write_samples(&samples).unwrap();
+            writer.finish().unwrap();
+        }
+
+        assert_eq!(std::fs::read(&path).unwrap(), [0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,150 @@
+use std::error::Error;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use quanta::Instant;
+use rodio::dynamic_mixer::{self, DynamicMixerController};
+use rodio::Sink;
+
+use crate::scavnet::audio::core::AudioOutput;
+use crate::scavnet::audio::tap::{AudioTaps, TappedMix};
+
+const PUMP_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Receives the mixed output of a `PumpedOutput` as interleaved samples.
+pub trait SampleWriter: Send {
+    fn name(&self) -> String;
+
+    fn write_samples(&mut self, samples: &[f32]) -> io::Result<()>;
+
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn uses_stdout(&self) -> bool {
+        false
+    }
+}
+
+/// An output without a sound card. A worker thread pulls the mix at the real-time rate, so
+/// playback takes as long as it would on a device, and hands it to a `SampleWriter`.
+pub struct PumpedOutput {
+    name: String,
+    uses_stdout: bool,
+    mixer: Arc<DynamicMixerController<f32>>,
+    taps: AudioTaps,
+    running: Arc<AtomicBool>,
+    pump: Option<JoinHandle<()>>,
+    errors: Receiver<String>,
+}
+
+impl PumpedOutput {
+    pub fn new(writer: impl SampleWriter + 'static, channels: u16, sample_rate: u32) -> Self {
+        let name = writer.name();
+        let uses_stdout = writer.uses_stdout();
+        let (mixer, mixer_rx) = dynamic_mixer::mixer::<f32>(channels, sample_rate);
+        let taps = AudioTaps::new(channels, sample_rate);
+        let mut mix = TappedMix::new(mixer_rx, taps.clone());
+        let running = Arc::new(AtomicBool::new(true));
+        let pump_running = running.clone();
+        let mut writer: Box<dyn SampleWriter> = Box::new(writer);
+        let (errors_tx, errors) = mpsc::channel();
+        let writer_name = name.clone();
+
+        let pump = thread::spawn(move || {
+            let frames_per_sec = sample_rate as f64;
+            let start_time = Instant::now();
+            let mut frames_written: u64 = 0;
+            let mut buffer: Vec<f32> = Vec::new();
+            let mut writing = true;
+
+            while pump_running.load(Ordering::Relaxed) {
+                let frames_due = (start_time.elapsed().as_secs_f64() * frames_per_sec) as u64;
+                let samples_due = (frames_due - frames_written) as usize * channels as usize;
+                buffer.clear();
+                buffer.extend(mix.by_ref().take(samples_due));
+                frames_written = frames_due;
+
+                // A closed pipe or full disk must not stall playback, so keep draining the mix.
+                if writing {
+                    if let Err(e) = writer.write_samples(&buffer) {
+                        let _ = errors_tx.send(format!("Audio output {} stopped writing ({}).", writer_name, e));
+                        writing = false;
+                    }
+                }
+                thread::sleep(PUMP_INTERVAL);
+            }
+
+            let _ = writer.finish();
+        });
+
+        Self {
+            name,
+            uses_stdout,
+            mixer,
+            taps,
+            running,
+            pump: Some(pump),
+            errors,
+        }
+    }
+}
+
+impl AudioOutput for PumpedOutput {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn new_sink(&self) -> Result<Sink, Box<dyn Error>> {
+        let (sink, queue_rx) = Sink::new_idle();
+        self.mixer.add(queue_rx);
+        Ok(sink)
+    }
+
+    fn taps(&self) -> AudioTaps {
+        self.taps.clone()
+    }
+
+    fn uses_stdout(&self) -> bool {
+        self.uses_stdout
+    }
+
+    fn take_errors(&self) -> Vec<String> {
+        self.errors.try_iter().collect()
+    }
+}
+
+impl Drop for PumpedOutput {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(pump) = self.pump.take() {
+            let _ = pump.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rodio::source::{SineWave, Source};
+
+    use crate::scavnet::audio::null::NullWriter;
+
+    #[test]
+    fn test_pumped_output_plays_in_real_time() {
+        let output = PumpedOutput::new(NullWriter, 2, 44100);
+        let sink = output.new_sink().unwrap();
+        let start_time = Instant::now();
+        sink.append(SineWave::new(440.0).take_duration(Duration::from_millis(200)));
+
+        sink.sleep_until_end();
+
+        assert!(sink.empty());
+        assert!(start_time.elapsed() >= Duration::from_millis(150));
+    }
+}
@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::io::Cursor;
+use std::time::Duration;
+
+use cpal::SampleRate;
+use rodio::source::{Source, UniformSourceIterator, WhiteNoise};
+use rodio::Decoder;
+
+use crate::scavnet::transmission::core::Transmission;
+
+// The rate hiss is generated at, as when played live.
+const HISS_SAMPLE_RATE: u32 = 44100;
+
+/// The transmission as it would be heard, hiss included, as interleaved samples in the given
+/// format.
+pub fn render_transmission(transmission: &Transmission, transmission_volume: f32, hiss_volume: f32, channels: u16, sample_rate: u32) -> Result<Vec<f32>, Box<dyn Error>> {
+    let mut samples = Vec::new();
+    samples.extend(hiss(transmission.hiss_preroll, hiss_volume, channels, sample_rate));
+    for item in &transmission.items {
+        let source = Decoder::new(Cursor::new(item.file_bytes.clone()))?
+            .convert_samples::<f32>()
+            .amplify(transmission_volume);
+        samples.extend(UniformSourceIterator::<_, f32>::new(source, channels, sample_rate));
+        samples.extend(hiss(item.sleep_after, hiss_volume, channels, sample_rate));
+    }
+    samples.extend(hiss(transmission.hiss_postroll, hiss_volume, channels, sample_rate));
+    Ok(samples)
+}
+
+fn hiss(secs: f32, volume: f32, channels: u16, sample_rate: u32) -> impl Iterator<Item = f32> {
+    let noise = WhiteNoise::new(SampleRate(HISS_SAMPLE_RATE))
+        .take_duration(Duration::from_secs_f32(secs.max(0.0)))
+        .amplify(volume);
+    UniformSourceIterator::new(noise, channels, sample_rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_hiss_only() {
+        let mut transmission = Transmission::new(0);
+        transmission.hiss_preroll = 0.5;
+        transmission.hiss_postroll = 0.25;
+
+        let samples = render_transmission(&transmission, 1.0, 0.5, 2, 8000).unwrap();
+
+        // 0.75 seconds of stereo at 8 kHz, give or take the resampler's edges.
+        assert!((samples.len() as i64 - 12000).abs() <= 8, "{}", samples.len());
+        assert!(samples.iter().all(|sample| sample.abs() <= 0.5));
+    }
+}
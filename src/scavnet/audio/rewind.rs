@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::scavnet::audio::tap::{AudioTaps, SampleTap};
+
+/// A section of the rewind buffer, ready to be played back.
+pub struct Replay {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+    /// Spectrum frames, each with its offset from the start of `samples`.
+    pub fft_frames: Vec<(Duration, Vec<f32>)>,
+}
+
+struct RewindState {
+    samples: VecDeque<i16>,
+    samples_written: u64,
+    fft_frames: VecDeque<(u64, Vec<f32>)>,
+}
+
+/// Keeps the last few minutes of the output mix, and the spectrum frames displayed with it,
+/// so they can be replayed. Samples are stored as 16-bit to halve the memory needed.
+pub struct RewindBuffer {
+    channels: u16,
+    sample_rate: u32,
+    capacity: usize,
+    state: Mutex<RewindState>,
+}
+
+impl RewindBuffer {
+    pub fn new(buffer_secs: u64, taps: &AudioTaps) -> Self {
+        let capacity = buffer_secs as usize * taps.sample_rate() as usize * taps.channels() as usize;
+        Self {
+            channels: taps.channels(),
+            sample_rate: taps.sample_rate(),
+            capacity,
+            state: Mutex::new(RewindState {
+                samples: VecDeque::with_capacity(capacity),
+                samples_written: 0,
+                fft_frames: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Records a spectrum frame as displayed at the current point in the mix.
+    pub fn record_fft_frame(&self, fft_frame: Vec<f32>) {
+        let mut state = self.state.lock();
+        let position = state.samples_written;
+        state.fft_frames.push_back((position, fft_frame));
+    }
+
+    /// The last `secs` seconds of the mix, or as much as has been kept.
+    pub fn replay(&self, secs: f32) -> Option<Replay> {
+        let state = self.state.lock();
+        let requested = (secs * self.sample_rate as f32) as usize * self.channels as usize;
+        let length = requested.min(state.samples.len());
+        if length == 0 {
+            return None;
+        }
+
+        let start_index = state.samples.len() - length;
+        let start_position = state.samples_written - length as u64;
+        let samples = state.samples.range(start_index..)
+            .map(|&sample| sample as f32 / i16::MAX as f32)
+            .collect();
+        let samples_per_sec = self.sample_rate as f64 * self.channels as f64;
+        let fft_frames = state.fft_frames.iter()
+            .filter(|(position, _)| *position >= start_position)
+            .map(|(position, fft_frame)| {
+                let offset = Duration::from_secs_f64((position - start_position) as f64 / samples_per_sec);
+                (offset, fft_frame.clone())
+            })
+            .collect();
+
+        Some(Replay {
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+            samples,
+            fft_frames,
+        })
+    }
+}
+
+impl SampleTap for RewindBuffer {
+    fn write_samples(&self, samples: &[f32], _channels: u16, _sample_rate: u32) {
+        let mut state = self.state.lock();
+        for &sample in samples {
+            if state.samples.len() >= self.capacity {
+                state.samples.pop_front();
+            }
+            state.samples.push_back((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16);
+        }
+        state.samples_written += samples.len() as u64;
+
+        let oldest_position = state.samples_written - state.samples.len() as u64;
+        while state.fft_frames.front().is_some_and(|(position, _)| *position < oldest_position) {
+            state.fft_frames.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_keeps_most_recent_audio() {
+        let buffer = RewindBuffer::new(1, &AudioTaps::new(1, 4));
+        buffer.write_samples(&[0.0, 0.0, 0.0, 0.0], 1, 4);
+        buffer.record_fft_frame(vec![1.0]);
+        buffer.write_samples(&[0.5, 0.5], 1, 4);
+        buffer.record_fft_frame(vec![2.0]);
+        buffer.write_samples(&[1.0, 1.0], 1, 4);
+
+        let replay = buffer.replay(1.0).unwrap();
+
+        assert_eq!(replay.samples.len(), 4);
+        assert!((replay.samples[0] - 0.5).abs() < 0.001);
+        assert_eq!(replay.samples[3], 1.0);
+        assert_eq!(replay.fft_frames.len(), 2);
+        assert_eq!(replay.fft_frames[1], (Duration::from_millis(500), vec![2.0]));
+    }
+
+    #[test]
+    fn test_replay_empty_buffer() {
+        let buffer = RewindBuffer::new(1, &AudioTaps::new(2, 44100));
+        assert!(buffer.replay(10.0).is_none());
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+
+use parking_lot::Mutex;
+
+use crate::scavnet::audio::tap::SampleTap;
+use crate::scavnet::bands::SpectrumBands;
+use crate::scavnet::fft::{spectrum_frame, HANN_WINDOW_SIZE};
+
+// Brings full-scale samples into the range of the SIGNAL chart.
+const LIVE_FFT_GAIN: f32 = 40.0;
+const LIVE_FFT_HOP_SIZE: usize = HANN_WINDOW_SIZE / 2;
+
+pub const SPECTRUM_SOURCE_LIVE: &str = "live";
+pub const SPECTRUM_SOURCE_PRECOMPUTED: &str = "precomputed";
+
+struct SpectrumInput {
+    samples: Vec<f32>,
+    sender: Sender<(Vec<f32>, u32)>,
+}
+
+/// Computes spectrum frames from the output mix as it plays, so the display follows what is
+/// actually heard: volume, hiss and all. The analysis runs on its own thread, away from the
+/// audio callback, and frames are grouped into `bands`.
+pub struct LiveSpectrum {
+    input: Mutex<SpectrumInput>,
+    latest_frame: Arc<Mutex<Option<Vec<f32>>>>,
+}
+
+impl LiveSpectrum {
+    pub fn new(bands: SpectrumBands) -> Self {
+        let (sender, receiver) = mpsc::channel::<(Vec<f32>, u32)>();
+        let latest_frame = Arc::new(Mutex::new(None));
+        let worker_frame = latest_frame.clone();
+
+        thread::spawn(move || {
+            while let Ok((window, sample_rate)) = receiver.recv() {
+                if let Some(frame) = spectrum_frame(&window, sample_rate) {
+                    let frame = bands.map(&frame, sample_rate);
+                    *worker_frame.lock() = Some(frame.iter().map(|x| x * LIVE_FFT_GAIN).collect());
+                }
+            }
+        });
+
+        Self {
+            input: Mutex::new(SpectrumInput {
+                samples: Vec::with_capacity(HANN_WINDOW_SIZE),
+                sender,
+            }),
+            latest_frame,
+        }
+    }
+
+    /// The newest frame computed since the last call, if any.
+    pub fn take_frame(&self) -> Option<Vec<f32>> {
+        self.latest_frame.lock().take()
+    }
+}
+
+impl SampleTap for LiveSpectrum {
+    fn write_samples(&self, samples: &[f32], channels: u16, sample_rate: u32) {
+        let mut input = self.input.lock();
+        for frame in samples.chunks(channels as usize) {
+            input.samples.push(frame.iter().sum::<f32>() / channels as f32);
+            if input.samples.len() == HANN_WINDOW_SIZE {
+                let window = input.samples.clone();
+                let _ = input.sender.send((window, sample_rate));
+                input.samples.drain(..LIVE_FFT_HOP_SIZE);
+            }
+        }
+    }
+}
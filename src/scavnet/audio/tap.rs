@@ -0,0 +1,111 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rodio::dynamic_mixer::DynamicMixer;
+use rodio::Source;
+
+const TAP_BUFFER_SAMPLES: usize = 1024;
+
+/// Observes the final mix of an output, as it is heard.
+pub trait SampleTap: Send + Sync {
+    fn write_samples(&self, samples: &[f32], channels: u16, sample_rate: u32);
+}
+
+/// The taps attached to an output, along with the format of the samples they receive.
+#[derive(Clone)]
+pub struct AudioTaps {
+    channels: u16,
+    sample_rate: u32,
+    taps: Arc<Mutex<Vec<Arc<dyn SampleTap>>>>,
+    suspended: Arc<AtomicBool>,
+}
+
+impl AudioTaps {
+    pub fn new(channels: u16, sample_rate: u32) -> Self {
+        Self {
+            channels,
+            sample_rate,
+            taps: Arc::new(Mutex::new(Vec::new())),
+            suspended: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn add(&self, tap: Arc<dyn SampleTap>) {
+        self.taps.lock().push(tap);
+    }
+
+    /// Taps observe the live mix. Suspend them while playing something that isn't live, such
+    /// as a replay from the rewind buffer.
+    pub fn set_suspended(&self, suspended: bool) {
+        self.suspended.store(suspended, Ordering::Relaxed);
+    }
+
+    pub fn write_samples(&self, samples: &[f32]) {
+        if self.suspended.load(Ordering::Relaxed) {
+            return;
+        }
+        for tap in self.taps.lock().iter() {
+            tap.write_samples(samples, self.channels, self.sample_rate);
+        }
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
+/// The mix of every sink on an output, passed through its taps. Plays silence rather than
+/// ending when no sinks are active, so it can be handed to a device once and left there.
+pub struct TappedMix {
+    mixer: DynamicMixer<f32>,
+    taps: AudioTaps,
+    buffer: Vec<f32>,
+}
+
+impl TappedMix {
+    pub fn new(mixer: DynamicMixer<f32>, taps: AudioTaps) -> Self {
+        Self {
+            mixer,
+            taps,
+            buffer: Vec::with_capacity(TAP_BUFFER_SAMPLES),
+        }
+    }
+}
+
+impl Iterator for TappedMix {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.mixer.next().unwrap_or(0.0);
+        self.buffer.push(sample);
+        if self.buffer.len() >= TAP_BUFFER_SAMPLES {
+            self.taps.write_samples(&self.buffer);
+            self.buffer.clear();
+        }
+        Some(sample)
+    }
+}
+
+impl Source for TappedMix {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.taps.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.taps.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
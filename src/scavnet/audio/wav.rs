@@ -0,0 +1,89 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+use hound::{SampleFormat, WavSpec, WavWriter};
+
+use crate::scavnet::audio::pump::SampleWriter;
+
+/// Writes the output to a directory of WAV files, starting a new timestamped file every
+/// `segment_secs` seconds so no single file grows without bound.
+pub struct RollingWavWriter {
+    dir: PathBuf,
+    segment_frames: u64,
+    spec: WavSpec,
+    writer: Option<WavWriter<BufWriter<File>>>,
+    frames_in_segment: u64,
+}
+
+impl RollingWavWriter {
+    pub fn new(dir: String, segment_secs: u64, channels: u16, sample_rate: u32) -> io::Result<Self> {
+        let dir = PathBuf::from(dir);
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            segment_frames: segment_secs.max(1).saturating_mul(sample_rate as u64),
+            spec: wav_spec(channels, sample_rate),
+            writer: None,
+            frames_in_segment: 0,
+        })
+    }
+
+    fn start_segment(&mut self) -> io::Result<()> {
+        self.finish()?;
+        let file_name = format!("scavnet-{}.wav", chrono::Local::now().format("%Y%m%d-%H%M%S%.3f"));
+        let writer = WavWriter::create(self.dir.join(file_name), self.spec).map_err(io::Error::other)?;
+        self.writer = Some(writer);
+        self.frames_in_segment = 0;
+        Ok(())
+    }
+}
+
+impl SampleWriter for RollingWavWriter {
+    fn name(&self) -> String {
+        format!("wav ({})", self.dir.display())
+    }
+
+    fn write_samples(&mut self, samples: &[f32]) -> io::Result<()> {
+        for frame in samples.chunks(self.spec.channels as usize) {
+            if self.writer.is_none() || self.frames_in_segment >= self.segment_frames {
+                self.start_segment()?;
+            }
+            let writer = self.writer.as_mut().unwrap();
+            for &sample in frame {
+                writer.write_sample(sample_to_i16(sample)).map_err(io::Error::other)?;
+            }
+            self.frames_in_segment += 1;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(writer) = self.writer.take() {
+            writer.finalize().map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a complete set of interleaved samples as a 16-bit WAV file.
+pub fn write_wav_file(path: &Path, samples: &[f32], channels: u16, sample_rate: u32) -> io::Result<()> {
+    let mut writer = WavWriter::create(path, wav_spec(channels, sample_rate)).map_err(io::Error::other)?;
+    for &sample in samples {
+        writer.write_sample(sample_to_i16(sample)).map_err(io::Error::other)?;
+    }
+    writer.finalize().map_err(io::Error::other)
+}
+
+fn wav_spec(channels: u16, sample_rate: u32) -> WavSpec {
+    WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    }
+}
+
+fn sample_to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
@@ -0,0 +1,174 @@
+use std::error::Error;
+
+use crate::scavnet::settings::{get_spectrum_band_settings, get_spectrum_display};
+
+pub const BAND_SCALE_LOG: &str = "log";
+pub const BAND_SCALE_MEL: &str = "mel";
+
+pub const SPECTRUM_DISPLAY_BARS: &str = "bars";
+pub const SPECTRUM_DISPLAY_WATERFALL: &str = "waterfall";
+pub const SPECTRUM_DISPLAY_BOTH: &str = "both";
+
+/// What the spectrum panel shows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpectrumDisplay {
+    Bars,
+    Waterfall,
+    Both,
+}
+
+impl SpectrumDisplay {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            SPECTRUM_DISPLAY_WATERFALL => Self::Waterfall,
+            SPECTRUM_DISPLAY_BOTH => Self::Both,
+            _ => Self::Bars,
+        }
+    }
+
+    pub fn from_settings() -> Self {
+        Self::from_name(&get_spectrum_display())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BandScale {
+    Log,
+    Mel,
+}
+
+impl BandScale {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            BAND_SCALE_MEL => Self::Mel,
+            _ => Self::Log,
+        }
+    }
+
+    fn scaled(self, freq: f32) -> f32 {
+        match self {
+            Self::Log => freq.ln(),
+            Self::Mel => 2595.0 * (1.0 + freq / 700.0).log10(),
+        }
+    }
+
+    fn unscaled(self, value: f32) -> f32 {
+        match self {
+            Self::Log => value.exp(),
+            Self::Mel => 700.0 * (10f32.powf(value / 2595.0) - 1.0),
+        }
+    }
+}
+
+/// Groups FFT bins into a fixed set of bands across a frequency range, so spectra of files
+/// with different sample rates line up on the same axis.
+#[derive(Clone, Debug)]
+pub struct SpectrumBands {
+    edges: Vec<f32>,
+}
+
+impl SpectrumBands {
+    pub fn new(scale: BandScale, min_freq: f32, max_freq: f32, count: usize) -> Self {
+        let min_freq = min_freq.max(1.0);
+        let max_freq = max_freq.max(min_freq + 1.0);
+        let count = count.max(1);
+        let (scale_min, scale_max) = (scale.scaled(min_freq), scale.scaled(max_freq));
+        let edges = (0..=count)
+            .map(|i| scale.unscaled(scale_min + (scale_max - scale_min) * i as f32 / count as f32))
+            .collect();
+        Self { edges }
+    }
+
+    pub fn from_settings() -> Result<Self, Box<dyn Error>> {
+        let (scale, min_freq, max_freq, count) = get_spectrum_band_settings()?;
+        Ok(Self::new(BandScale::from_name(&scale), min_freq, max_freq, count))
+    }
+
+    pub fn count(&self) -> usize {
+        self.edges.len() - 1
+    }
+
+    /// The frequency at `position` bands along the axis, from 0 to `count()`.
+    pub fn frequency_at(&self, position: usize) -> f32 {
+        self.edges[position.min(self.count())]
+    }
+
+    /// Maps the bins of a spectrum, from 0 Hz to half `sample_rate`, into bands. Each band takes
+    /// its loudest bin; bands narrower than a bin take the bin at their centre, and bands above
+    /// the source's range are empty.
+    pub fn map(&self, bins: &[f32], sample_rate: u32) -> Vec<f32> {
+        if bins.len() < 2 {
+            return vec![0.0; self.count()];
+        }
+        let nyquist = sample_rate as f32 / 2.0;
+        let bin_width = nyquist / (bins.len() - 1) as f32;
+
+        self.edges.windows(2)
+            .map(|edges| {
+                let (low, high) = (edges[0], edges[1]);
+                if low >= nyquist {
+                    return 0.0;
+                }
+                let first_bin = (low / bin_width).ceil() as usize;
+                let last_bin = ((high / bin_width).floor() as usize).min(bins.len() - 1);
+                if first_bin <= last_bin {
+                    bins[first_bin..=last_bin].iter().copied().fold(0.0, f32::max)
+                } else {
+                    let centre_bin = (((low + high) / 2.0) / bin_width).round() as usize;
+                    bins[centre_bin.min(bins.len() - 1)]
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn frequency_label(freq: f32) -> String {
+    if freq >= 1000.0 {
+        format!("{:.1}KHz", freq / 1000.0)
+    } else {
+        format!("{:.0}Hz", freq)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_edges() {
+        let bands = SpectrumBands::new(BandScale::Log, 100.0, 10000.0, 2);
+        assert_eq!(bands.count(), 2);
+        assert!((bands.frequency_at(0) - 100.0).abs() < 0.1);
+        assert!((bands.frequency_at(1) - 1000.0).abs() < 0.1);
+        assert!((bands.frequency_at(2) - 10000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_map_is_independent_of_sample_rate() {
+        let bands = SpectrumBands::new(BandScale::Mel, 200.0, 3000.0, 8);
+        // A single 1 KHz peak, at 100 Hz per bin and at 50 Hz per bin.
+        let mut coarse_bins = vec![0.0; 41];
+        coarse_bins[10] = 1.0;
+        let mut fine_bins = vec![0.0; 81];
+        fine_bins[20] = 1.0;
+
+        let coarse = bands.map(&coarse_bins, 8000);
+        let fine = bands.map(&fine_bins, 8000);
+
+        assert_eq!(coarse, fine);
+        assert_eq!(coarse.iter().filter(|&&value| value == 1.0).count(), 1);
+    }
+
+    #[test]
+    fn test_map_above_nyquist_is_empty() {
+        let bands = SpectrumBands::new(BandScale::Log, 100.0, 8000.0, 4);
+        let mapped = bands.map(&[1.0; 65], 4000);
+        assert_eq!(mapped.last(), Some(&0.0));
+    }
+
+    #[test]
+    fn test_frequency_label() {
+        assert_eq!(frequency_label(500.0), "500Hz");
+        assert_eq!(frequency_label(3500.0), "3.5KHz");
+    }
+}
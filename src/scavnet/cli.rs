@@ -0,0 +1,136 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::scavnet::settings::SETTINGS_PATH;
+
+// How long `simulate` plans for when not told, in seconds.
+const SIMULATE_DURATION: f32 = 3600.0;
+
+#[derive(Debug, Parser)]
+#[command(name = "scavnet", version, about = "A radio scanner for a world that isn't there.")]
+pub struct Cli {
+    /// What to do; runs the scanner if left out.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the scanner.
+    Run(RunArgs),
+    /// Check the settings, networks and transmission library, reporting every problem found.
+    Validate(ConfigArgs),
+    /// Precompute spectrum analyses of the transmission library.
+    Analyze(AnalyzeArgs),
+    /// Write a conversation, with its hiss, to a WAV file.
+    Render(RenderArgs),
+    /// Print what the director would queue, without playing anything.
+    Simulate(SimulateArgs),
+    /// List the audio output devices.
+    Devices,
+}
+
+/// Where settings and data are read from.
+#[derive(Debug, Args)]
+pub struct ConfigArgs {
+    /// Settings file, with or without its extension.
+    #[arg(short, long, default_value = SETTINGS_PATH)]
+    pub config: String,
+    /// Data directory, in place of `paths.data_dir`.
+    #[arg(long)]
+    pub data_dir: Option<String>,
+}
+
+impl Default for ConfigArgs {
+    fn default() -> Self {
+        Self {
+            config: SETTINGS_PATH.to_string(),
+            data_dir: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    /// Seed for the director's choices of set and conversation, to pick the same ones in the same
+    /// order.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Enable debug mode, as `debug = true` does.
+    #[arg(long)]
+    pub debug: bool,
+    /// Print a line per event instead of drawing the interface, and discard audio unless
+    /// `--audio-output` is given.
+    #[arg(long)]
+    pub headless: bool,
+    /// Print a line per event instead of drawing the interface.
+    #[arg(long)]
+    pub plain: bool,
+    /// Audio output, in place of `audio.output`.
+    #[arg(long)]
+    pub audio_output: Option<String>,
+    /// Audio device, in place of `audio.output_device`.
+    #[arg(long)]
+    pub audio_device: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct AnalyzeArgs {
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    /// Afterwards, remove analyses of files no longer in the library.
+    #[arg(long)]
+    pub prune: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RenderArgs {
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    /// The set file the conversation is in, as shown in the library browser.
+    #[arg(long)]
+    pub set: String,
+    /// The conversation's id.
+    #[arg(long)]
+    pub conversation: String,
+    /// WAV file to write.
+    #[arg(short, long)]
+    pub output: PathBuf,
+    /// Seed for the hiss lengths.
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Args)]
+pub struct SimulateArgs {
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    /// Seconds of scanning to plan.
+    #[arg(long, default_value_t = SIMULATE_DURATION)]
+    pub duration: f32,
+    /// Seed for the schedule, to repeat a simulation.
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cli() {
+        let cli = Cli::try_parse_from(["scavnet", "run", "--config", "Other.toml", "--seed", "7", "--headless"]).unwrap();
+        let Some(Command::Run(run)) = cli.command else {
+            panic!("Expected the run command.");
+        };
+        assert_eq!(run.config.config, "Other.toml");
+        assert_eq!(run.seed, Some(7));
+        assert!(run.headless && !run.debug);
+
+        assert!(Cli::try_parse_from(["scavnet"]).unwrap().command.is_none());
+        assert!(Cli::try_parse_from(["scavnet", "render", "--set", "a.yaml"]).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::path::Path;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+
+use crate::scavnet::analysis::LibraryAnalysis;
+use crate::scavnet::audio::core::{OUTPUT_DEVICE, OUTPUT_NULL, OUTPUT_PCM, OUTPUT_WAV};
+use crate::scavnet::audio::render::render_transmission;
+use crate::scavnet::audio::wav::write_wav_file;
+use crate::scavnet::director::Director;
+use crate::scavnet::fft::prune_fft_cache;
+use crate::scavnet::keys::init_key_map;
+use crate::scavnet::layout::init_layouts;
+use crate::scavnet::networks::RadioNetworks;
+use crate::scavnet::scanner::frequency_display;
+use crate::scavnet::settings::{get_archive_settings, get_audio_format, get_audio_output, get_audio_wav_settings, get_cache_dir, get_hiss_postroll_times, get_hiss_preroll_times, get_key_bindings, get_rewind_buffer_time, get_spectrum_band_settings, get_web_settings};
+use crate::scavnet::settings::{get_layout_settings, get_theme_settings, get_volumes, network_library_path, transmission_data_dir, transmission_library_path};
+use crate::scavnet::theme::init_themes;
+use crate::scavnet::time::rand_time_secs;
+use crate::scavnet::transmission::interfaces::core::build_transmission;
+use crate::scavnet::transmission::interfaces::localfilesystem::TransmissionLocalFileSystem;
+use crate::scavnet::transmission::library::{LibraryRowKind, TransmissionLibrary};
+use crate::scavnet::transmission::sets::TransmissionSet;
+
+const ANALYSIS_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Prints every problem found with the settings, networks and transmission library, failing if
+/// there were any.
+pub async fn validate() -> Result<(), Box<dyn Error>> {
+    let mut problems = Vec::new();
+
+    let (theme_name, theme_dir) = get_theme_settings();
+    problems.extend(init_themes(Path::new(&theme_dir), &theme_name));
+    problems.extend(init_key_map(&get_key_bindings()));
+    let (layout_mode, layout_descriptions) = get_layout_settings();
+    problems.extend(init_layouts(&layout_mode, &layout_descriptions));
+    let audio_output = get_audio_output();
+    if ![OUTPUT_DEVICE, OUTPUT_NULL, OUTPUT_WAV, OUTPUT_PCM].contains(&audio_output.as_str()) {
+        problems.push(format!("Invalid audio output: {}", audio_output));
+    }
+    if let Err(e) = get_audio_format() {
+        problems.push(e.to_string());
+    }
+    if let Err(e) = get_audio_wav_settings() {
+        problems.push(e.to_string());
+    }
+    if let Err(e) = get_web_settings() {
+        problems.push(e.to_string());
+    }
+    if let Err(e) = get_archive_settings() {
+        problems.push(e.to_string());
+    }
+    if let Err(e) = get_rewind_buffer_time() {
+        problems.push(e.to_string());
+    }
+    if let Err(e) = get_spectrum_band_settings() {
+        problems.push(e.to_string());
+    }
+
+    let network_path = network_library_path();
+    if let Err(e) = RadioNetworks::from_yaml(&network_path).await {
+        problems.push(format!("Networks {}: {}", network_path, e));
+    }
+
+    let library_path = transmission_library_path();
+    match TransmissionLibrary::build(Path::new(&library_path)) {
+        Ok(library) => {
+            let mut set_paths = BTreeSet::new();
+            for row in library.rows() {
+                match row.kind {
+                    LibraryRowKind::Conversation { set_path, .. } => {
+                        set_paths.insert(set_path);
+                    }
+                    LibraryRowKind::Error(e) => problems.push(format!("Set {}: {}", row.label, e)),
+                    LibraryRowKind::Set => {}
+                }
+            }
+            for set_path in set_paths {
+                problems.extend(validate_set(&set_path));
+            }
+        }
+        Err(e) => problems.push(format!("Transmission library {}: {}", library_path, e)),
+    }
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    if problems.is_empty() {
+        println!("No problems found.");
+        Ok(())
+    } else {
+        Err(format!("{} problems found.", problems.len()).into())
+    }
+}
+
+// Problems with the conversations in a set that only show once one is built.
+fn validate_set(set_path: &str) -> Vec<String> {
+    let set = match TransmissionSet::from_yaml(set_path) {
+        Ok(set) => set,
+        Err(e) => return vec![format!("Set {}: {}", set_path, e)],
+    };
+
+    let mut problems = Vec::new();
+    for conversation in &set.conversations {
+        let name = format!("{} in {}", conversation.id, set_path);
+        match conversation.interface.as_str() {
+            "LocalFileSystem" => {
+                for item in &conversation.transmissions.items {
+                    match TransmissionLocalFileSystem::item_file_path(item) {
+                        Ok(file_path) => {
+                            if let Err(e) = hound::WavReader::open(&file_path) {
+                                problems.push(format!("Conversation {}, item {}: {}: {}", name, item.id, file_path, e));
+                            }
+                        }
+                        Err(e) => problems.push(format!("Conversation {}, item {}: {}", name, item.id, e)),
+                    }
+                }
+            }
+            "OpenAI" => {}
+            interface => problems.push(format!("Conversation {}: invalid interface '{}'.", name, interface)),
+        }
+    }
+    problems
+}
+
+/// Analyses the transmission library for the precomputed spectrum, then, with `prune`, removes
+/// analyses of files no longer in it.
+pub fn analyze(prune: bool) -> Result<(), Box<dyn Error>> {
+    let analysis = LibraryAnalysis::start(transmission_data_dir().into(), get_cache_dir().into());
+    while !analysis.progress().finished {
+        eprint!("\r{}", analysis.progress().summary());
+        std::thread::sleep(ANALYSIS_POLL_INTERVAL);
+    }
+    eprintln!();
+    for event in analysis.take_events() {
+        println!("{}", event);
+    }
+
+    if prune {
+        let cache_dir = get_cache_dir();
+        let (removed, errors) = prune_fft_cache(Path::new(&transmission_data_dir()), Path::new(&cache_dir))?;
+        for error in &errors {
+            eprintln!("Could not read {}", error);
+        }
+        match errors.len() {
+            0 => println!("Removed {} cache entries from {}.", removed, cache_dir),
+            _ => println!("Skipped pruning {}, as not every library file could be read.", cache_dir),
+        }
+    }
+
+    match analysis.progress().failed.len() {
+        0 => Ok(()),
+        failed => Err(format!("{} files could not be analysed.", failed).into()),
+    }
+}
+
+/// Writes `conversation` from the set at `set_path` to `output` as heard on the scanner, in the
+/// configured audio format. The set is only read, so the conversation isn't marked played.
+pub async fn render(set_path: &str, conversation_id: &str, output: &Path, rng: &mut StdRng) -> Result<(), Box<dyn Error>> {
+    let network_path = network_library_path();
+    let networks = RadioNetworks::from_yaml(&network_path).await
+        .map_err(|e| format!("Networks {}: {}", network_path, e))?;
+    let set = TransmissionSet::from_yaml(set_path)
+        .map_err(|e| format!("Set {}: {}", set_path, e))?;
+    let conversation = set.conversations.iter()
+        .find(|conversation| conversation.id == conversation_id)
+        .ok_or(format!("No conversation '{}' in {}.", conversation_id, set_path))?
+        .clone();
+
+    let (preroll_min, preroll_max) = get_hiss_preroll_times();
+    let (postroll_min, postroll_max) = get_hiss_postroll_times();
+    let hiss_preroll = rand_time_secs(rng, preroll_min, preroll_max);
+    let hiss_postroll = rand_time_secs(rng, postroll_min, postroll_max);
+    let transmission = build_transmission(conversation, networks, hiss_preroll, hiss_postroll).await?;
+
+    let (transmission_volume, hiss_volume) = get_volumes();
+    let (sample_rate, channels) = get_audio_format()?;
+    let samples = render_transmission(&transmission, transmission_volume, hiss_volume, channels, sample_rate)?;
+    write_wav_file(output, &samples, channels, sample_rate)?;
+
+    let secs = samples.len() as f32 / (channels as u32 * sample_rate) as f32;
+    println!("Rendered {} ({:.1} seconds) to {}.", conversation_id, secs, output.display());
+    Ok(())
+}
+
+/// Prints the transmissions the director would queue in the first `duration` seconds.
+pub async fn simulate(duration: f32, rng: &mut StdRng) -> Result<(), Box<dyn Error>> {
+    let director = Director::new(rng.clone()).await
+        .map_err(|e| format!("Error initializing Director: {}", e))?;
+    let schedule = director.schedule(rng, duration)?;
+
+    for transmission in &schedule {
+        let network = director.get_networks()
+            .network_name_from_freq(transmission.frequency as u64)
+            .unwrap_or("Unknown".to_string());
+        println!(
+            "{} set=\"{}\" conversation=\"{}\" frequency=\"{}\" network=\"{}\"",
+            clock(transmission.at),
+            transmission.set_path,
+            transmission.conversation,
+            frequency_display(transmission.frequency),
+            network
+        );
+    }
+    println!("{} transmissions in {}.", schedule.len(), clock(duration));
+    Ok(())
+}
+
+// Seconds from the start as `+HH:MM:SS`.
+fn clock(secs: f32) -> String {
+    let secs = secs as u64;
+    format!("+{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock() {
+        assert_eq!(clock(0.4), "+00:00:00");
+        assert_eq!(clock(3725.0), "+01:02:05");
+    }
+}
@@ -1,7 +1,14 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
 use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
 
+use chrono::{DateTime, Local};
+use parking_lot::Mutex;
 use quanta::Instant;
 use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 use rand::SeedableRng;
 
 use crate::scavnet::networks::RadioNetworks;
@@ -11,10 +18,64 @@ use crate::scavnet::time::{rand_time_from_now, rand_time_secs};
 use crate::scavnet::transmission::core::Transmission;
 use crate::scavnet::transmission::interfaces::core::build_transmission;
 use crate::scavnet::transmission::library::build_transmission_library;
-use crate::scavnet::transmission::library::TransmissionLibrary;
+use crate::scavnet::transmission::library::{LibraryRow, TransmissionLibrary};
 use crate::scavnet::transmission::queue::TransmissionQueue;
 use crate::scavnet::transmission::sets::{TransmissionSet, Conversation};
 
+/// What the builder task should build next.
+#[derive(Clone, Debug)]
+pub enum BuildRequest {
+    /// A conversation chosen from the library by weight.
+    Random,
+    /// A given conversation, played as soon as it is built, on `frequency` if set.
+    Cue { set_path: String, conversation: String, frequency: Option<u32> },
+}
+
+// Library selections kept for the inspector.
+const SELECTION_HISTORY_LENGTH: usize = 5;
+
+#[derive(Clone, Debug)]
+pub enum BuilderStatus {
+    Idle,
+    Building,
+    Built(String),
+    Failed(String),
+}
+
+/// A conversation picked from the library, with the branches taken to reach it.
+#[derive(Clone, Debug)]
+pub struct LibrarySelection {
+    pub selected_at: DateTime<Local>,
+    pub path: Vec<(String, f64, f64)>,
+    pub conversation: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct QueuedTransmission {
+    pub id: String,
+    pub frequency: u32,
+    pub age: Duration,
+    pub items: usize,
+}
+
+/// A transmission planned by `Director::schedule`, `at` seconds from the start.
+#[derive(Clone, Debug)]
+pub struct ScheduledTransmission {
+    pub at: f32,
+    pub set_path: String,
+    pub conversation: String,
+    pub frequency: u32,
+}
+
+/// What the director is doing, for the inspector panel.
+#[derive(Clone, Debug)]
+pub struct DirectorInspection {
+    pub next_queue_in: Duration,
+    pub queue: Vec<QueuedTransmission>,
+    pub builder_status: BuilderStatus,
+    pub selections: Vec<LibrarySelection>,
+}
+
 #[derive(Clone)]
 pub struct Director {
     network_path: String,
@@ -30,6 +91,9 @@ pub struct Director {
     hiss_preroll_max_time: f32,
     hiss_postroll_min_time: f32,
     hiss_postroll_max_time: f32,
+    // Shared with the clone in the builder task.
+    builder_status: Arc<Mutex<BuilderStatus>>,
+    selections: Arc<Mutex<VecDeque<LibrarySelection>>>,
 }
 
 impl Director {
@@ -50,6 +114,8 @@ impl Director {
             hiss_preroll_max_time: 0.0,
             hiss_postroll_min_time: 0.0,
             hiss_postroll_max_time: 0.0,
+            builder_status: Arc::new(Mutex::new(BuilderStatus::Idle)),
+            selections: Arc::new(Mutex::new(VecDeque::with_capacity(SELECTION_HISTORY_LENGTH))),
         }
     }
 
@@ -128,26 +194,129 @@ impl Director {
         Instant::now() >= self.next_queue_time
     }
 
-    pub async fn get_new_transmission(&self) -> Transmission {
-        let new_transmission = self.get_random_transmission().await.unwrap();
-        new_transmission
+    /// Builds the transmission asked for, or `None` if it failed; the reason is kept for the
+    /// inspector.
+    pub async fn get_new_transmission(&mut self, request: BuildRequest) -> Option<Transmission> {
+        *self.builder_status.lock() = BuilderStatus::Building;
+        let result = match request {
+            BuildRequest::Random => self.get_random_transmission().await,
+            BuildRequest::Cue { set_path, conversation, frequency } => self.get_cued_transmission(&set_path, &conversation, frequency).await,
+        };
+        match result {
+            Ok(new_transmission) => {
+                *self.builder_status.lock() = BuilderStatus::Built(new_transmission.id.clone());
+                Some(new_transmission)
+            }
+            Err(e) => {
+                *self.builder_status.lock() = BuilderStatus::Failed(e.to_string());
+                None
+            }
+        }
     }
 
-    pub async fn get_random_transmission(&self) -> Result<Transmission, Box<dyn Error>> {
-        let conversation = self.get_random_conversation();
-        let hiss_preroll = rand_time_secs(&mut self.rng.clone(), self.hiss_preroll_min_time, self.hiss_preroll_max_time);
-        let hiss_postroll = rand_time_secs(&mut self.rng.clone(), self.hiss_postroll_min_time, self.hiss_postroll_max_time);
-        build_transmission(conversation, self.networks.clone(), hiss_preroll, hiss_postroll).await
+    pub fn inspect(&self) -> DirectorInspection {
+        let now = Instant::now();
+        DirectorInspection {
+            next_queue_in: self.next_queue_time.saturating_duration_since(now),
+            queue: self.queue.transmissions.iter()
+                .map(|transmission| QueuedTransmission {
+                    id: transmission.id.clone(),
+                    frequency: transmission.frequency,
+                    age: now.saturating_duration_since(transmission.created_at),
+                    items: transmission.items.len(),
+                })
+                .collect(),
+            builder_status: self.builder_status.lock().clone(),
+            selections: self.selections.lock().iter().cloned().collect(),
+        }
+    }
+
+    pub async fn get_random_transmission(&mut self) -> Result<Transmission, Box<dyn Error>> {
+        let (set_path, conversation) = self.get_random_conversation();
+        let hiss_preroll = rand_time_secs(&mut self.rng, self.hiss_preroll_min_time, self.hiss_preroll_max_time);
+        let hiss_postroll = rand_time_secs(&mut self.rng, self.hiss_postroll_min_time, self.hiss_postroll_max_time);
+        let mut transmission = build_transmission(conversation, self.networks.clone(), hiss_preroll, hiss_postroll).await?;
+        transmission.set_path = Some(set_path);
+        Ok(transmission)
     }
     
-    fn get_random_conversation(&self) -> Conversation {
+    async fn get_cued_transmission(&mut self, set_path: &str, conversation_id: &str, frequency: Option<u32>) -> Result<Transmission, Box<dyn Error>> {
+        let mut set = TransmissionSet::from_yaml(set_path)?;
+        let conversation = set.take_conversation(conversation_id)
+            .ok_or(format!("No conversation '{}' in {}.", conversation_id, set_path))?
+            .clone();
+        let hiss_preroll = rand_time_secs(&mut self.rng, self.hiss_preroll_min_time, self.hiss_preroll_max_time);
+        let hiss_postroll = rand_time_secs(&mut self.rng, self.hiss_postroll_min_time, self.hiss_postroll_max_time);
+        let mut transmission = build_transmission(conversation, self.networks.clone(), hiss_preroll, hiss_postroll).await?;
+        if let Some(frequency) = frequency {
+            transmission.frequency = frequency;
+        }
+        transmission.cued = true;
+        transmission.set_path = Some(set_path.to_string());
+        Ok(transmission)
+    }
+
+    pub fn library_rows(&self) -> Vec<LibraryRow> {
+        self.library.rows()
+    }
+
+    /// The transmissions that would be queued in the first `duration` seconds, chosen with `rng`
+    /// by library weight and each set's playback order. Sets are only read, so nothing is marked
+    /// played.
+    pub fn schedule(&self, rng: &mut StdRng, duration: f32) -> Result<Vec<ScheduledTransmission>, Box<dyn Error>> {
+        let frequencies = self.networks.scan_frequencies();
+        // Sets stay loaded, so each plays through its conversations as it would in a run, without
+        // saving what was played.
+        let mut sets: HashMap<String, TransmissionSet> = HashMap::new();
+        let mut scheduled = Vec::new();
+        let mut at = rand_time_secs(rng, self.min_queue_delay, self.max_queue_delay);
+        while at < duration {
+            let mut path = Vec::new();
+            let set_path = self.library.choose(rng, &mut path).data.clone().unwrap_or_default();
+            let set = match sets.entry(set_path.clone()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(TransmissionSet::from_yaml(&set_path)?),
+            };
+            let conversation = set.next_conversation(rng)
+                .ok_or(format!("No conversation to play in {}.", set_path))?;
+            let frequency = if conversation.transmissions.random_frequency {
+                frequencies.choose(rng).copied().unwrap_or(0)
+            } else {
+                conversation.transmissions.frequency
+            };
+            scheduled.push(ScheduledTransmission {
+                at,
+                set_path,
+                conversation: conversation.id.clone(),
+                frequency,
+            });
+            at += rand_time_secs(rng, self.min_queue_delay, self.max_queue_delay);
+        }
+        Ok(scheduled)
+    }
+
+    fn get_random_conversation(&mut self) -> (String, Conversation) {
+        let mut path = Vec::new();
         let path_string = {
-            let selected_node = self.library.choose(&mut self.rng.clone());
+            let selected_node = self.library.choose(&mut self.rng, &mut path);
             selected_node.data.clone().unwrap()
         };
         let mut set = TransmissionSet::from_yaml(&path_string).unwrap();
-        let conversation = set.get_conversation().unwrap();
-        conversation.clone()
+        let conversation = set.get_conversation(&mut self.rng).unwrap();
+        self.record_selection(path, conversation.id.clone());
+        (path_string.clone(), conversation.clone())
+    }
+
+    fn record_selection(&self, path: Vec<(String, f64, f64)>, conversation: String) {
+        let mut selections = self.selections.lock();
+        if selections.len() == SELECTION_HISTORY_LENGTH {
+            selections.pop_back();
+        }
+        selections.push_front(LibrarySelection {
+            selected_at: Local::now(),
+            path,
+            conversation,
+        });
     }
 
 }
@@ -1,70 +1,95 @@
+use std::collections::HashSet;
 use std::error::Error;
 use std::fs::{self, File};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::io::{self, BufWriter, BufReader};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use hound;
+use quanta::Instant;
 use rayon::prelude::*;
 use rmp_serde::{encode::write, decode::from_read};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use spectrum_analyzer::{samples_fft_to_spectrum, FrequencyLimit};
 use spectrum_analyzer::scaling::divide_by_N_sqrt;
 use spectrum_analyzer::windows::hann_window;
+use walkdir::WalkDir;
 
-const HANN_WINDOW_SIZE: usize = 2048;
+pub const HANN_WINDOW_SIZE: usize = 2048;
 const FFT_MAX_VALUE: f32 = 120.0;
 const FFT_NORMALIZATION_FACTOR: f32 = 2.5;
 
-#[derive(Serialize, Deserialize)]
-struct FFTData(Vec<Vec<f32>>);
-
-pub fn write_fft_all_wav_files_in_dir(dir: &Path) {
-    let mut files = fs::read_dir(dir).unwrap();
-    while let Some(file) = files.next() {
-        let file = file.unwrap();
-        let path = file.path();
-        if path.is_file() {
-            if path.extension().unwrap() == "wav" {
-                let path_str = path.to_str().unwrap();
-                if let Err(e) = write_fft_data(&path_str) {
-                    eprintln!("Error writing FFT data: {}", e);
-                }
-            }
-        } else if path.is_dir() {
-            write_fft_all_wav_files_in_dir(&path);
+const FFT_CACHE_FORMAT_VERSION: u32 = 3;
+const FFT_CACHE_SUBDIR: &str = "fft";
+const FFT_CACHE_EXTENSION: &str = "fft";
+const FFT_SOURCE_EXTENSION: &str = "source";
+// A source modified this close to when it was hashed may have changed again without its size or
+// modification time changing, so its recorded hash isn't trusted.
+const FFT_SOURCE_RACY_NANOS: u64 = 2_000_000_000;
+
+/// Describes what a cached analysis was computed from. A cache is only used while its header
+/// matches the source content and the current analysis parameters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct FFTCacheHeader {
+    format_version: u32,
+    source_hash: String,
+    window_size: usize,
+    max_value: f32,
+    normalization_factor: f32,
+}
+
+impl FFTCacheHeader {
+    fn for_source(source_hash: &str) -> Self {
+        Self {
+            format_version: FFT_CACHE_FORMAT_VERSION,
+            source_hash: source_hash.to_string(),
+            window_size: HANN_WINDOW_SIZE,
+            max_value: FFT_MAX_VALUE,
+            normalization_factor: FFT_NORMALIZATION_FACTOR,
         }
     }
+
+    fn is_current(&self, source_hash: &str) -> bool {
+        *self == Self::for_source(source_hash)
+    }
 }
 
-pub fn write_fft_data(file_path: &str) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
-    let normalized_data = fft_from_path(file_path)?;
-    let fft_data_filepath = format!("{}.fft", file_path);
+#[derive(Serialize, Deserialize)]
+struct FFTCache {
+    header: FFTCacheHeader,
+    data: Vec<Vec<f32>>,
+}
 
-    // Scale the data from 0 to 100
-    let scaled_data: Vec<Vec<f32>> = normalized_data.iter()
-        .map(|v| {
-            let max = v.iter().copied().reduce(f32::max).unwrap_or(f32::MIN);
-            v.iter().map(|x| x / max * 120.0).collect()
-        })
-        .collect();
+/// The content hash of a source file as of its recorded size and modification time, so the
+/// file only has to be hashed again once they change.
+#[derive(Serialize, Deserialize)]
+struct FFTSourceStamp {
+    path: String,
+    size: u64,
+    modified: u64,
+    hashed: u64,
+    source_hash: String,
+}
 
-    let file = File::create(&fft_data_filepath)?;
-    let mut writer = BufWriter::new(file);
-    write(&mut writer, &FFTData(scaled_data.clone()))?;
+impl FFTSourceStamp {
+    fn is_current(&self, file_path: &str, size: u64, modified: u64) -> bool {
+        self.path == file_path
+            && self.size == size
+            && self.modified == modified
+            && self.hashed.saturating_sub(modified) > FFT_SOURCE_RACY_NANOS
+    }
+}
 
+pub fn write_fft_data(file_path: &str, cache_dir: &Path) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    let normalized_data = fft_normalized_from_path(file_path)?;
+    write_fft_cache(&stamped_source_hash(file_path, cache_dir)?, &normalized_data, cache_dir)?;
     Ok(normalized_data)
 }
 
-pub fn read_fft_data(file_path: &str) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
-    let fft_data_filepath = format!("{}.fft", file_path);
-    // If the file doesn't exist, return an error
-    if !std::path::Path::new(&fft_data_filepath).exists() {
-        return Err(Box::from("FFT data file does not exist"));
-    }
-    let file = File::open(&fft_data_filepath)?;
-    let reader = BufReader::new(file);
-    let fft_data: FFTData = from_read(reader)?;
-    Ok(fft_data.0)
+/// Reads the cached analysis of `file_path`, failing if there is none or it is stale.
+pub fn read_fft_data(file_path: &str, cache_dir: &Path) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    read_fft_cache(&stamped_source_hash(file_path, cache_dir)?, cache_dir)
 }
 
 pub fn fft_normalized_from_path(file_path: &str) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
@@ -72,15 +97,96 @@ pub fn fft_normalized_from_path(file_path: &str) -> Result<Vec<Vec<f32>>, Box<dy
     fft_normalized(&mut reader)
 }
 
-pub fn fft_cached_normalized(file_path: &str) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
-    let fft_data = read_fft_data(file_path);
-    if fft_data.is_ok() {
-        return Ok(fft_data.unwrap());
+/// The cached analysis of `file_path`, rebuilding the cache if it is missing or stale.
+pub fn fft_cached_normalized(file_path: &str, cache_dir: &Path) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    let source_hash = stamped_source_hash(file_path, cache_dir)?;
+    if let Ok(fft_data) = read_fft_cache(&source_hash, cache_dir) {
+        return Ok(fft_data);
     }
     let fft_data = fft_normalized_from_path(file_path)?;
+    // A cache that can't be written only costs time on the next run, so it isn't an error.
+    let _ = write_fft_cache(&source_hash, &fft_data, cache_dir);
     Ok(fft_data)
 }
 
+/// Removes cached analyses in `cache_dir` that no longer match any WAV file under
+/// `library_dir`. Returns how many were removed, along with the WAV files that couldn't be read.
+/// If any couldn't be read nothing is removed, as their analyses can't be told from stale ones.
+pub fn prune_fft_cache(library_dir: &Path, cache_dir: &Path) -> Result<(usize, Vec<String>), Box<dyn Error>> {
+    let mut library_hashes = HashSet::new();
+    let mut errors = Vec::new();
+    for entry in WalkDir::new(library_dir).into_iter().filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.extension().is_none_or(|extension| extension != "wav") {
+            continue;
+        }
+        let Some(path_str) = path.to_str() else {
+            continue;
+        };
+        match stamped_source_hash(path_str, cache_dir) {
+            Ok(source_hash) => {
+                library_hashes.insert(source_hash);
+            }
+            Err(e) => errors.push(format!("{}: {}", path_str, e)),
+        }
+    }
+
+    let mut removed = 0;
+    let fft_cache_dir = cache_dir.join(FFT_CACHE_SUBDIR);
+    if !errors.is_empty() || !fft_cache_dir.exists() {
+        return Ok((removed, errors));
+    }
+    for entry in fs::read_dir(fft_cache_dir)? {
+        let path = entry?.path();
+        // Stamps only save hashing, so they go with their source but aren't counted.
+        if path.extension().is_some_and(|extension| extension == FFT_SOURCE_EXTENSION) {
+            if !read_source_stamp(&path).is_ok_and(|stamp| Path::new(&stamp.path).exists() && library_hashes.contains(&stamp.source_hash)) {
+                fs::remove_file(&path)?;
+            }
+            continue;
+        }
+        let source_hash = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+        if !library_hashes.contains(&source_hash) || read_fft_cache(&source_hash, cache_dir).is_err() {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok((removed, errors))
+}
+
+fn fft_cache_path(source_hash: &str, cache_dir: &Path) -> PathBuf {
+    cache_dir.join(FFT_CACHE_SUBDIR).join(format!("{}.{}", source_hash, FFT_CACHE_EXTENSION))
+}
+
+fn write_fft_cache(source_hash: &str, normalized_data: &[Vec<f32>], cache_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let fft_data_filepath = fft_cache_path(source_hash, cache_dir);
+    let cache = FFTCache {
+        header: FFTCacheHeader::for_source(source_hash),
+        data: normalized_data.to_vec(),
+    };
+
+    fs::create_dir_all(cache_dir.join(FFT_CACHE_SUBDIR))?;
+    let file = File::create(&fft_data_filepath)?;
+    let mut writer = BufWriter::new(file);
+    write(&mut writer, &cache)?;
+    Ok(())
+}
+
+fn read_fft_cache(source_hash: &str, cache_dir: &Path) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    let fft_data_filepath = fft_cache_path(source_hash, cache_dir);
+    // If the file doesn't exist, return an error
+    if !fft_data_filepath.exists() {
+        return Err(Box::from("FFT data file does not exist"));
+    }
+    let file = File::open(&fft_data_filepath)?;
+    let reader = BufReader::new(file);
+    let fft_cache: FFTCache = from_read(reader)?;
+    if !fft_cache.header.is_current(source_hash) {
+        return Err(Box::from("FFT data file is stale"));
+    }
+    Ok(fft_cache.data)
+}
+
 pub fn fft_normalized(reader: &mut hound::WavReader<io::BufReader<File>>) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
     let fft_data = fft(reader)?;
     let normalized_data = normalize_fft_data(fft_data)?;
@@ -108,11 +214,6 @@ pub fn normalize_fft_data(fft_data: Vec<Vec<f32>>) -> Result<Vec<Vec<f32>>, Box<
     Ok(normalized_data)
 }
 
-pub fn fft_from_path(file_path: &str) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
-    let mut reader: hound::WavReader<io::BufReader<File>> = hound::WavReader::open(file_path)?;
-    fft(&mut reader)
-}
-
 pub fn fft(reader: &mut hound::WavReader<io::BufReader<File>>) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
     let wav_sample_rate = reader.spec().sample_rate;
     let samples: Vec<f32> = reader.samples::<i16>()
@@ -132,25 +233,239 @@ pub fn fft(reader: &mut hound::WavReader<io::BufReader<File>>) -> Result<Vec<Vec
             if sample_index_end > len_samples {
                 return None;
             }
-            let sample_set: Vec<f32> = samples[sample_index_start..sample_index_end].to_vec();
-
-            let hann_window = hann_window(&sample_set);
-            let spectrum_hann_window = samples_fft_to_spectrum(
-                &hann_window,
-                wav_sample_rate,
-                FrequencyLimit::All,
-                Some(&divide_by_N_sqrt),
-            ).ok()?;
-
-            // Convert the spectrum to a Vec<f32>
-            let fft_data_item: Vec<f32> = spectrum_hann_window.data()
-                .iter()
-                .map(|(_fr, fr_val)| fr_val.val())
-                .collect();
-
-            Some(fft_data_item)
+            spectrum_frame(&samples[sample_index_start..sample_index_end], wav_sample_rate)
         })
         .collect();
 
     Ok(fft_data)
-}
\ No newline at end of file
+}
+
+/// The magnitude spectrum of one `HANN_WINDOW_SIZE` window of samples.
+pub fn spectrum_frame(samples: &[f32], sample_rate: u32) -> Option<Vec<f32>> {
+    let hann_window = hann_window(samples);
+    let spectrum_hann_window = samples_fft_to_spectrum(
+        &hann_window,
+        sample_rate,
+        FrequencyLimit::All,
+        Some(&divide_by_N_sqrt),
+    ).ok()?;
+
+    // Convert the spectrum to a Vec<f32>
+    let fft_data_item: Vec<f32> = spectrum_hann_window.data()
+        .iter()
+        .map(|(_fr, fr_val)| fr_val.val())
+        .collect();
+
+    Some(fft_data_item)
+}
+
+/// Steps through an item's precomputed FFT frames in time with its playback.
+pub struct FFTFrameStepper<'a> {
+    frames: &'a [Vec<f32>],
+    frame_duration: Option<Duration>,
+    next_frame_time: Option<Instant>,
+    index: usize,
+}
+
+impl<'a> FFTFrameStepper<'a> {
+    pub fn new(frames: &'a [Vec<f32>], file_duration: f32, start_time: Instant) -> Self {
+        // With the live spectrum there may be no precomputed data to step through.
+        let frame_duration = (!frames.is_empty())
+            .then(|| Duration::from_secs_f32(file_duration / frames.len() as f32));
+        Self {
+            frames,
+            frame_duration,
+            next_frame_time: frame_duration.map(|duration| start_time + duration),
+            index: 0,
+        }
+    }
+
+    /// The frame that is due at `now`, if any.
+    pub fn next_frame(&mut self, now: Instant) -> Option<&'a Vec<f32>> {
+        if now < self.next_frame_time? {
+            return None;
+        }
+        let frame = self.frames.get(self.index);
+        self.index += 1;
+        self.next_frame_time = self.frame_duration
+            .filter(|_| self.index < self.frames.len())
+            .map(|duration| now + duration);
+        frame
+    }
+}
+
+/// The content hash of `file_path`, hashing it only if it changed since it was last hashed.
+fn stamped_source_hash(file_path: &str, cache_dir: &Path) -> Result<String, Box<dyn Error>> {
+    let metadata = fs::metadata(file_path)?;
+    let (size, modified) = (metadata.len(), nanos_since_epoch(metadata.modified()?));
+    let stamp_path = fft_source_path(file_path, cache_dir);
+    if let Ok(stamp) = read_source_stamp(&stamp_path) {
+        if stamp.is_current(file_path, size, modified) {
+            return Ok(stamp.source_hash);
+        }
+    }
+
+    let stamp = FFTSourceStamp {
+        path: file_path.to_string(),
+        size,
+        modified,
+        hashed: nanos_since_epoch(SystemTime::now()),
+        source_hash: source_hash(file_path)?,
+    };
+    // Like the cache itself, a stamp that can't be written only costs time on the next run.
+    let _ = write_source_stamp(&stamp_path, &stamp);
+    Ok(stamp.source_hash)
+}
+
+fn fft_source_path(file_path: &str, cache_dir: &Path) -> PathBuf {
+    let path_hash = format!("{:x}", Sha256::digest(file_path.as_bytes()));
+    cache_dir.join(FFT_CACHE_SUBDIR).join(format!("{}.{}", path_hash, FFT_SOURCE_EXTENSION))
+}
+
+fn read_source_stamp(stamp_path: &Path) -> Result<FFTSourceStamp, Box<dyn Error>> {
+    let reader = BufReader::new(File::open(stamp_path)?);
+    Ok(from_read(reader)?)
+}
+
+fn write_source_stamp(stamp_path: &Path, stamp: &FFTSourceStamp) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = stamp_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(File::create(stamp_path)?);
+    write(&mut writer, stamp)?;
+    Ok(())
+}
+
+fn nanos_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+fn source_hash(file_path: &str) -> Result<String, Box<dyn Error>> {
+    let mut file = File::open(file_path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scavnet::testing::test_dir;
+
+    fn write_test_wav(file_path: &str, frequency: f32) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(file_path, spec).unwrap();
+        for i in 0..(HANN_WINDOW_SIZE * 4) {
+            let t = i as f32 / 8000.0;
+            writer.write_sample(((t * frequency * 2.0 * std::f32::consts::PI).sin() * 10000.0) as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_cached_data_matches_fresh_computation() {
+        let scratch = test_dir();
+        let dir = scratch.path();
+        let cache_dir = dir.join("cache");
+        let file_path = dir.join("tone.wav");
+        let file_path = file_path.to_str().unwrap();
+        write_test_wav(file_path, 440.0);
+
+        let fresh_data = fft_normalized_from_path(file_path).unwrap();
+        let first_run_data = fft_cached_normalized(file_path, &cache_dir).unwrap();
+        let cached_run_data = fft_cached_normalized(file_path, &cache_dir).unwrap();
+        let cached_data = read_fft_data(file_path, &cache_dir).unwrap();
+
+        assert_eq!(first_run_data, fresh_data);
+        assert_eq!(cached_run_data, fresh_data);
+        assert_eq!(cached_data, fresh_data);
+    }
+
+    #[test]
+    fn test_replaced_source_is_not_read_from_cache() {
+        let scratch = test_dir();
+        let dir = scratch.path();
+        let cache_dir = dir.join("cache");
+        let file_path = dir.join("tone.wav");
+        let file_path = file_path.to_str().unwrap();
+        write_test_wav(file_path, 440.0);
+        write_fft_data(file_path, &cache_dir).unwrap();
+
+        // Rewritten in the same instant, the source may keep its size and modification time
+        // on filesystems with coarse timestamps, so only hashing its content shows it changed.
+        write_test_wav(file_path, 1000.0);
+        let stale_result = read_fft_data(file_path, &cache_dir);
+        let rebuilt_data = fft_cached_normalized(file_path, &cache_dir).unwrap();
+        let cached_data = read_fft_data(file_path, &cache_dir).unwrap();
+
+        assert!(stale_result.is_err());
+        assert_eq!(rebuilt_data, cached_data);
+    }
+
+    #[test]
+    fn test_prune_removes_entries_without_source() {
+        let scratch = test_dir();
+        let dir = scratch.path();
+        let library_dir = dir.join("transmissions");
+        let cache_dir = dir.join("cache");
+        fs::create_dir_all(&library_dir).unwrap();
+        let kept_path = library_dir.join("kept.wav");
+        let removed_path = library_dir.join("removed.wav");
+        write_test_wav(kept_path.to_str().unwrap(), 440.0);
+        write_test_wav(removed_path.to_str().unwrap(), 1000.0);
+        write_fft_data(kept_path.to_str().unwrap(), &cache_dir).unwrap();
+        write_fft_data(removed_path.to_str().unwrap(), &cache_dir).unwrap();
+        fs::remove_file(&removed_path).unwrap();
+
+        let (removed, errors) = prune_fft_cache(&library_dir, &cache_dir).unwrap();
+        let kept_result = read_fft_data(kept_path.to_str().unwrap(), &cache_dir);
+
+        assert_eq!(removed, 1);
+        assert!(errors.is_empty());
+        assert!(kept_result.is_ok());
+    }
+
+    #[test]
+    fn test_frame_stepper_follows_playback() {
+        let frames = vec![vec![1.0], vec![2.0]];
+        let start_time = Instant::now();
+        let mut stepper = FFTFrameStepper::new(&frames, 2.0, start_time);
+
+        assert_eq!(stepper.next_frame(start_time), None);
+        let first_time = start_time + Duration::from_secs(1);
+        assert_eq!(stepper.next_frame(first_time), Some(&vec![1.0]));
+        assert_eq!(stepper.next_frame(first_time + Duration::from_secs(1)), Some(&vec![2.0]));
+        assert_eq!(stepper.next_frame(first_time + Duration::from_secs(10)), None);
+    }
+
+    #[test]
+    fn test_frame_stepper_without_frames() {
+        let start_time = Instant::now();
+        let mut stepper = FFTFrameStepper::new(&[], 2.0, start_time);
+
+        assert_eq!(stepper.next_frame(start_time + Duration::from_secs(1)), None);
+    }
+
+    #[test]
+    fn test_prune_reports_unreadable_source() {
+        let scratch = test_dir();
+        let dir = scratch.path();
+        let library_dir = dir.join("transmissions");
+        let cache_dir = dir.join("cache");
+        fs::create_dir_all(library_dir.join("unreadable.wav")).unwrap();
+        let removed_path = library_dir.join("removed.wav");
+        write_test_wav(removed_path.to_str().unwrap(), 1000.0);
+        write_fft_data(removed_path.to_str().unwrap(), &cache_dir).unwrap();
+        fs::remove_file(&removed_path).unwrap();
+
+        let (removed, errors) = prune_fft_cache(&library_dir, &cache_dir).unwrap();
+
+        assert_eq!(removed, 0);
+        assert_eq!(errors.len(), 1);
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Local};
+
+use crate::scavnet::transmission::core::Transmission;
+
+/// A transmission heard this session.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub received_at: DateTime<Local>,
+    pub frequency_display: String,
+    pub network: String,
+    pub conversation: String,
+    pub captions: Vec<String>,
+    /// The set the conversation came from, to build it again and play it once more.
+    pub set_path: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn new(transmission: &Transmission, frequency_display: String, network: String) -> Self {
+        Self {
+            received_at: Local::now(),
+            frequency_display,
+            network,
+            conversation: transmission.id.clone(),
+            captions: transmission.items.iter().map(|item| item.caption.clone()).collect(),
+            set_path: transmission.set_path.clone(),
+        }
+    }
+
+    /// Whether `query` appears in the entry's network, conversation, frequency or captions,
+    /// ignoring case.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [&self.network, &self.conversation, &self.frequency_display]
+            .into_iter()
+            .chain(self.captions.iter())
+            .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+/// Every transmission heard this session, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct SessionHistory {
+    entries: Vec<HistoryEntry>,
+}
+
+impl SessionHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, entry: HistoryEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&HistoryEntry> {
+        self.entries.get(index)
+    }
+
+    /// Entries matching `query` with their indexes, newest first. An empty query matches
+    /// everything.
+    pub fn search(&self, query: &str) -> Vec<(usize, &HistoryEntry)> {
+        self.entries.iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, entry)| query.is_empty() || entry.matches(query))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(conversation: &str, captions: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            received_at: Local::now(),
+            frequency_display: "150.00000 MHz".to_string(),
+            network: "Police".to_string(),
+            conversation: conversation.to_string(),
+            captions: captions.iter().map(|caption| caption.to_string()).collect(),
+            set_path: None,
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let mut history = SessionHistory::new();
+        history.add(entry("patrol-1", &["Unit two, respond to the docks."]));
+        history.add(entry("patrol-2", &["All clear."]));
+
+        let all: Vec<&str> = history.search("").iter().map(|(_, entry)| entry.conversation.as_str()).collect();
+        let docks: Vec<usize> = history.search("DOCKS").iter().map(|(index, _)| *index).collect();
+
+        assert_eq!(all, ["patrol-2", "patrol-1"]);
+        assert_eq!(docks, [0]);
+        assert_eq!(history.get(0).unwrap().conversation, "patrol-1");
+        assert_eq!(history.search("police").len(), 2);
+        assert!(history.search("fire").is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+use crossterm::event::{Event, EventStream, KeyEvent, MouseEvent};
+use futures::StreamExt;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+/// Terminal input the interface reacts to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEvent {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    Resize(u16, u16),
+}
+
+impl InputEvent {
+    pub fn from_event(event: Event) -> Option<Self> {
+        match event {
+            Event::Key(key) => Some(InputEvent::Key(key)),
+            Event::Mouse(mouse) => Some(InputEvent::Mouse(mouse)),
+            Event::Resize(width, height) => Some(InputEvent::Resize(width, height)),
+            _ => None,
+        }
+    }
+}
+
+/// Reads terminal events in the background for as long as the receiver is kept. Events are
+/// queued, so none are lost between frames.
+pub fn spawn_input_task() -> UnboundedReceiver<InputEvent> {
+    let (input_tx, input_rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut events = EventStream::new();
+        while let Some(event) = events.next().await {
+            let Ok(event) = event else {
+                break;
+            };
+            if let Some(input) = InputEvent::from_event(event) {
+                if input_tx.send(input).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    input_rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn test_input_event_from_event() {
+        let key = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+
+        assert_eq!(InputEvent::from_event(Event::Key(key)), Some(InputEvent::Key(key)));
+        assert_eq!(InputEvent::from_event(Event::Resize(80, 24)), Some(InputEvent::Resize(80, 24)));
+        assert_eq!(InputEvent::from_event(Event::FocusGained), None);
+    }
+}
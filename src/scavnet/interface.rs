@@ -1,8 +1,9 @@
 use std::{
-    collections::HashMap,
     io::{
+        stderr,
         stdout,
-        Stdout
+        IsTerminal,
+        Write,
     }
 };
 
@@ -16,14 +17,19 @@ use crossterm::{
     ExecutableCommand,
     event::{
         self,
+        DisableMouseCapture,
+        EnableMouseCapture,
         KeyCode,
         KeyEvent,
-        KeyModifiers,
+        MouseButton,
+        MouseEvent,
+        MouseEventKind,
     }
 };
 
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 use ratatui::{
   prelude::{
     Color,
@@ -32,17 +38,31 @@ use ratatui::{
     Terminal,
   },
   Frame,
+  layout::{
+    Position,
+    Rect,
+  },
   widgets::{
     Block, Borders, Padding
   },
 };
 
+use crate::scavnet::interfaces::components::{band, director, system as system_panel};
+use crate::scavnet::input::{spawn_input_task, InputEvent};
+use crate::scavnet::keys::{key_map, Action, KeyBinding, KeyScope};
 use crate::scavnet::scanner::Scanner;
+use crate::scavnet::settings::{is_debug, is_mouse_enabled};
 use crate::scavnet::system::System;
+use crate::scavnet::theme::{next_theme, theme};
+use crate::scavnet::web::WebServer;
 
 use super::{
     interfaces::{
+        help,
+        history,
+        library,
         main,
+        plain::PlainEvent,
     },
 };
 
@@ -50,26 +70,66 @@ lazy_static! {
     pub static ref NAVIGATION_STATE: Mutex<InterfaceNavigationState> = Mutex::new(
       InterfaceNavigationState{
         window: InterfaceWindow::Main,
-        region: InterfaceRegion::None
+        region: InterfaceRegion::None,
+        scroll: 0,
+        selected: 0,
+        help: false,
       }
     );
     static ref SIG_EXIT: Mutex<bool> = Mutex::new(false);
+    static ref PLAYBACK_REQUEST: Mutex<Option<PlaybackRequest>> = Mutex::new(None);
+    static ref CUE_REQUEST: Mutex<Option<CueRequest>> = Mutex::new(None);
+    static ref SCANNER_REQUEST: Mutex<Option<ScannerRequest>> = Mutex::new(None);
+    static ref REPLAY_REQUEST: Mutex<Option<usize>> = Mutex::new(None);
+    static ref VOLUME: Mutex<f32> = Mutex::new(1.0);
+    static ref VOLUME_DRAGGING: Mutex<bool> = Mutex::new(false);
+    pub static ref MOUSE_TARGETS: Mutex<MouseTargets> = Mutex::new(MouseTargets::default());
 
-    pub static ref STATE_COLORS: HashMap<InterfaceRegionState, Color> = {
-        let mut m = HashMap::new();
-        m.insert(InterfaceRegionState::Active, Color::Yellow);
-        m.insert(InterfaceRegionState::Inactive, Color::White);
-        m
-    };
+    pub static ref SEARCH_INPUT_BUFFER: Mutex<String> = Mutex::new(String::new());
+}
 
-    pub static ref BLOCK_DEFAULT: Block<'static> = Block::default()
-        .borders(Borders::ALL)
-        .padding(Padding::new(1, 1, 1, 1))
-        .border_style(Style::default()
-        .fg(Color::White)
-    );
+const PAGE_LINES: usize = 10;
+const WHEEL_LINES: usize = 3;
 
-    pub static ref SEARCH_INPUT_BUFFER: Mutex<String> = Mutex::new(String::new());
+/// Where clickable parts of the screen were last drawn. Cleared before each frame, so only those
+/// on screen respond.
+#[derive(Clone, Debug, Default)]
+pub struct MouseTargets {
+    /// The band overview, with the columns each network spans.
+    pub band: Option<(Rect, Vec<(u16, u16)>)>,
+    pub system_log: Option<Rect>,
+    /// The transcript, with the history entry shown on each line.
+    pub transcript: Option<(Rect, Vec<Option<usize>>)>,
+    pub volume: Option<Rect>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaybackRequest {
+    /// Replay the last given number of seconds from the rewind buffer.
+    Rewind(f32),
+    /// Stop replaying and return to live audio.
+    Live,
+    /// Cut the transmission playing short.
+    Skip,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScannerRequest {
+    /// Stop scanning on the current frequency, or resume.
+    Hold,
+    /// Move the given number of scan frequencies while holding.
+    Tune(i64),
+    /// Hold on the start of the network at the given index.
+    JumpToNetwork(usize),
+}
+
+/// A conversation picked in the library browser to be played next.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CueRequest {
+    /// The library browser row picked.
+    pub row: usize,
+    /// Play it on the scanner's current frequency rather than its own.
+    pub on_current_frequency: bool,
 }
 
 #[derive(Clone, Hash, Eq, PartialEq)]
@@ -80,31 +140,95 @@ pub enum InterfaceRegionState {
 
 #[derive(Clone, PartialEq)]
 pub struct InterfaceNavigationState {
+    /// The main window panel with focus, which sees keys before the global bindings.
     pub region: InterfaceRegion,
     pub window: InterfaceWindow,
+    /// Lines scrolled from the top of the current window's list, or in the focused panel.
+    pub scroll: usize,
+    /// The highlighted row of the current window's list, or in the focused panel.
+    pub selected: usize,
+    /// Whether the key bindings are shown over the window.
+    pub help: bool,
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum InterfaceWindow {
     Main,
+    History,
+    Library,
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum InterfaceRegion {
-    Drop1,
-    Loop1,
-    Loop2,
-    Mutations,
+    Band,
+    System,
+    Director,
     None,
 }
 
+impl InterfaceRegion {
+    /// Panels that can take focus, in the order of their number keys.
+    pub const FOCUSABLE: [InterfaceRegion; 3] = [
+        InterfaceRegion::Band,
+        InterfaceRegion::System,
+        InterfaceRegion::Director,
+    ];
+
+    /// The panel's place in the focus order, from 1.
+    pub fn number(&self) -> Option<usize> {
+        Self::FOCUSABLE.iter().position(|region| region == self).map(|index| index + 1)
+    }
+
+    /// The key that focuses the panel, shown on its border.
+    pub fn focus_key(&self) -> String {
+        let action = match self {
+            InterfaceRegion::Band => Action::FocusBand,
+            InterfaceRegion::System => Action::FocusSystem,
+            InterfaceRegion::Director => Action::FocusDirector,
+            InterfaceRegion::None => return String::new(),
+        };
+        key_map().hint(action)
+    }
+
+    fn is_available(&self) -> bool {
+        match self {
+            InterfaceRegion::Director => is_debug(),
+            _ => true,
+        }
+    }
+
+    /// What the panel's keys do, shown on its border while focused.
+    pub fn key_hints(&self) -> String {
+        let key_map = key_map();
+        match self {
+            InterfaceRegion::Band => band::key_hints(&key_map),
+            InterfaceRegion::System => system_panel::key_hints(&key_map),
+            InterfaceRegion::Director => director::key_hints(&key_map),
+            InterfaceRegion::None => String::new(),
+        }
+    }
+
+    fn react_to_action(&self, navigation_state: &mut InterfaceNavigationState, action: Action) {
+        match self {
+            InterfaceRegion::Band => band::react_to_action(navigation_state, action),
+            InterfaceRegion::System => system_panel::react_to_action(navigation_state, action),
+            InterfaceRegion::Director => director::react_to_action(navigation_state, action),
+            InterfaceRegion::None => {},
+        }
+    }
+}
+
 pub struct MainInterface {
-    pub terminal: Terminal<CrosstermBackend<Stdout>>,
+    /// Not set in plain mode, where events are printed as lines instead.
+    terminal: Option<Terminal<CrosstermBackend<Box<dyn Write>>>>,
+    use_stderr: bool,
+    input: UnboundedReceiver<InputEvent>,
+    web: Option<WebServer>,
 }
 
 impl InterfaceNavigationState {
     pub fn get_region_state(&self, region: InterfaceRegion) -> InterfaceRegionState {
-        if NAVIGATION_STATE.lock().region == region {
+        if self.region == region {
             InterfaceRegionState::Active
         } else {
             InterfaceRegionState::Inactive
@@ -112,91 +236,390 @@ impl InterfaceNavigationState {
     }
 
     pub fn get_region_color(&self, region: InterfaceRegion) -> Color {
-        STATE_COLORS.get(&self.get_region_state(region)).unwrap().clone()
+        let theme = theme();
+        match self.get_region_state(region) {
+            InterfaceRegionState::Active => theme.highlight,
+            InterfaceRegionState::Inactive => theme.border,
+        }
+    }
+
+    fn focus(&mut self, region: InterfaceRegion) {
+        if region.is_available() {
+            self.region = region;
+            self.scroll = 0;
+            self.selected = 0;
+        }
+    }
+
+    /// The key map scopes listened to in the open window, and by the focused panel.
+    pub fn key_scopes(&self) -> Vec<KeyScope> {
+        match (self.window, self.region) {
+            (InterfaceWindow::Main, InterfaceRegion::None) => vec![KeyScope::Global, KeyScope::Main],
+            (InterfaceWindow::Main, _) => vec![KeyScope::Global, KeyScope::Main, KeyScope::Panel],
+            (InterfaceWindow::History, _) => vec![KeyScope::Global, KeyScope::Window],
+            (InterfaceWindow::Library, _) => vec![KeyScope::Global, KeyScope::Window, KeyScope::Library],
+        }
+    }
+
+    // Opens `window` from any other, or returns from it to the main window.
+    fn toggle_window(&mut self, window: InterfaceWindow) {
+        self.focus(InterfaceRegion::None);
+        self.window = if self.window == window { InterfaceWindow::Main } else { window };
+        self.help = false;
     }
+
+    // Moves focus to the next available panel, or from the last to none.
+    fn focus_next(&mut self) {
+        let next = InterfaceRegion::FOCUSABLE.iter()
+            .skip(self.region.number().unwrap_or(0))
+            .find(|region| region.is_available())
+            .copied()
+            .unwrap_or(InterfaceRegion::None);
+        self.region = next;
+        self.scroll = 0;
+        self.selected = 0;
+    }
+}
+
+/// Keeps the focused panel's scroll within `max` and returns it.
+pub fn clamp_scroll(max: usize) -> usize {
+    let mut navigation_state = NAVIGATION_STATE.lock();
+    navigation_state.scroll = navigation_state.scroll.min(max);
+    navigation_state.scroll
+}
+
+/// Keeps the focused panel's selection within `max` and returns it.
+pub fn clamp_selected(max: usize) -> usize {
+    let mut navigation_state = NAVIGATION_STATE.lock();
+    navigation_state.selected = navigation_state.selected.min(max);
+    navigation_state.selected
+}
+
+/// The output volume set with the volume bar, from 0 to 1, applied on top of the configured
+/// volumes.
+pub fn volume() -> f32 {
+    *VOLUME.lock()
+}
+
+/// Asks the main loop to move the scanner.
+pub fn request_scanner(request: ScannerRequest) {
+    *SCANNER_REQUEST.lock() = Some(request);
+}
+
+/// The bordered block panels are drawn in, in the current theme.
+pub fn block_default() -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .padding(Padding::new(1, 1, 1, 1))
+        .border_style(Style::default().fg(theme().border))
 }
 
 impl MainInterface {
-    pub fn new() -> Self {
+    /// Draws on stderr when `use_stderr` is set, leaving stdout free for piped audio. With `plain`,
+    /// or when that stream isn't a terminal, prints a line per event there instead.
+    pub fn new(use_stderr: bool, plain: bool) -> Self {
+        let is_terminal = if use_stderr { stderr().is_terminal() } else { stdout().is_terminal() };
+        if plain || !is_terminal {
+            // Without raw mode, Ctrl-C arrives as a signal rather than a key.
+            tokio::spawn(async {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    *SIG_EXIT.lock() = true;
+                }
+            });
+            return Self {
+                terminal: None,
+                use_stderr,
+                input: unbounded_channel().1,
+                web: None,
+            };
+        }
+
         let _ = enable_raw_mode();
-        let _ = stdout().execute(EnterAlternateScreen);
-        let terminal = Terminal::new(CrosstermBackend::new(stdout())).unwrap();
+        let mut writer = Self::writer(use_stderr);
+        let _ = writer.execute(EnterAlternateScreen);
+        if is_mouse_enabled() {
+            let _ = writer.execute(EnableMouseCapture);
+        }
+        let terminal = Terminal::new(CrosstermBackend::new(writer)).unwrap();
 
-        Self { 
-            terminal
+        Self {
+            terminal: Some(terminal),
+            use_stderr,
+            input: spawn_input_task(),
+            web: None,
+        }
+    }
+
+    pub fn is_plain(&self) -> bool {
+        self.terminal.is_none()
+    }
+
+    /// Prints `event` as a line in plain mode. The UI shows the same in its own way, so this does
+    /// nothing otherwise.
+    pub fn announce(&mut self, event: PlainEvent) {
+        if self.is_plain() {
+            let time = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f").to_string();
+            let _ = writeln!(Self::writer(self.use_stderr), "{}", event.line(&time));
+        }
+    }
+
+    /// Mirrors each drawn frame to the browser front-end.
+    pub fn attach_web_server(&mut self, web: WebServer) {
+        self.web = Some(web);
+    }
+
+    fn writer(use_stderr: bool) -> Box<dyn Write> {
+        if use_stderr {
+            Box::new(stderr())
+        } else {
+            Box::new(stdout())
         }
     }
 
     pub fn draw(&mut self, scanner: &Scanner, system: &System) {
         let state = NAVIGATION_STATE.lock().clone();
         match state.window {
+            _ if self.is_plain() => {}
             InterfaceWindow::Main => {
                 self.render(main::ui, scanner, system);
             }
-            // InterfaceWindow::Search => {
-            //     self.render(search::ui);
-            // }
+            InterfaceWindow::History => {
+                self.render(history::ui, scanner, system);
+            }
+            InterfaceWindow::Library => {
+                self.render(library::ui, scanner, system);
+            }
+        }
+        if let Some(web) = &mut self.web {
+            web.publish(scanner, system);
         }
     }
 
     pub fn cleanup(&mut self) {
+        if self.is_plain() {
+            return;
+        }
         let _ = disable_raw_mode();
-        let _ = stdout().execute(LeaveAlternateScreen);
+        let mut writer = Self::writer(self.use_stderr);
+        if is_mouse_enabled() {
+            let _ = writer.execute(DisableMouseCapture);
+        }
+        let _ = writer.execute(LeaveAlternateScreen);
     }
 
     pub fn get_exit(&self) -> bool{
-        SIG_EXIT.lock().clone()
+        *SIG_EXIT.lock()
+    }
+
+    pub fn take_playback_request(&self) -> Option<PlaybackRequest> {
+        PLAYBACK_REQUEST.lock().take()
+    }
+
+    pub fn take_scanner_request(&self) -> Option<ScannerRequest> {
+        SCANNER_REQUEST.lock().take()
+    }
+
+    /// The index of a history entry clicked in the transcript, to play again.
+    pub fn take_replay_request(&self) -> Option<usize> {
+        REPLAY_REQUEST.lock().take()
+    }
+
+    pub fn take_cue_request(&self) -> Option<CueRequest> {
+        CUE_REQUEST.lock().take()
+    }
+
+    pub fn is_library_open(&self) -> bool {
+        NAVIGATION_STATE.lock().window == InterfaceWindow::Library
     }
 
     fn render(&mut self, render_callback: fn(&mut Frame, &Scanner, &System), scanner: &Scanner, system: &System) {
-        let _ = self.terminal.draw(|frame| {
+        let theme = theme();
+        *MOUSE_TARGETS.lock() = MouseTargets::default();
+        let Some(terminal) = &mut self.terminal else {
+            return;
+        };
+        let _ = terminal.draw(|frame| {
+            frame.render_widget(Block::new().style(Style::new().bg(theme.background).fg(theme.text)), frame.area());
             render_callback(frame, scanner, system);
+            let navigation_state = NAVIGATION_STATE.lock().clone();
+            if navigation_state.help {
+                help::overlay(frame, &navigation_state.key_scopes());
+            }
         });
     }
 
-    pub fn react_to_key_events(&mut self) {
-        let key = {
-            let key_event = super::super::LAST_KEYEVENT.lock();
-            if key_event.code == KeyCode::Null && key_event.modifiers == KeyModifiers::NONE {
-                return;
+    /// Handles all input received since the last call.
+    pub fn react_to_input_events(&mut self) {
+        while let Ok(input) = self.input.try_recv() {
+            match input {
+                InputEvent::Key(key) => Self::react_to_key_event(key),
+                InputEvent::Resize(_, _) => {
+                    if let Some(terminal) = &mut self.terminal {
+                        let _ = terminal.autoresize();
+                    }
+                },
+                InputEvent::Mouse(mouse) => Self::react_to_mouse_event(mouse),
             }
-            key_event.clone()
-        };
-
-        *super::super::LAST_KEYEVENT.lock() = KeyEvent::new(KeyCode::Null, KeyModifiers::NONE);
+        }
+    }
 
+    fn react_to_mouse_event(mouse: MouseEvent) {
+        let targets = MOUSE_TARGETS.lock().clone();
+        let position = Position::new(mouse.column, mouse.row);
         let mut navigation_state = NAVIGATION_STATE.lock();
 
-        if key.kind == event::KeyEventKind::Press {
-            match navigation_state.window {
-                InterfaceWindow::Main => {
-                    match navigation_state.region {
-                        InterfaceRegion::Loop1 => {
-                            if let KeyCode::Char('a') = key.code {
-                                // navigation_state.window = InterfaceWindow::Search;
-                            }
-                        },
-                        InterfaceRegion::None => {
-                            if let KeyCode::Char('q') = key.code {
-                                *SIG_EXIT.lock() = true;
-                            }
-                        },
-                        _ => {},
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(area) = targets.volume.filter(|area| area.contains(position)) {
+                    *VOLUME_DRAGGING.lock() = true;
+                    Self::set_volume_from_column(area, mouse.column);
+                } else if let Some((area, networks)) = targets.band.filter(|(area, _)| area.contains(position)) {
+                    let column = mouse.column - area.x;
+                    if let Some(index) = networks.iter().position(|(start, end)| (*start..*end).contains(&column)) {
+                        request_scanner(ScannerRequest::JumpToNetwork(index));
                     }
-
-                    match key.code {
-                        KeyCode::Esc => {
-                            navigation_state.region = InterfaceRegion::None;
-                        },
-                        KeyCode::Char(c) if ('1'..='9').contains(&c) => {
-                            let trigger = c.to_string().parse::<usize>().unwrap();
-                        },
-                        _ => {},
+                } else if let Some((area, line_entries)) = targets.transcript.filter(|(area, _)| area.contains(position)) {
+                    if let Some(Some(index)) = line_entries.get((mouse.row - area.y) as usize) {
+                        *REPLAY_REQUEST.lock() = Some(*index);
+                    }
+                }
+            },
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some(area) = targets.volume.filter(|_| *VOLUME_DRAGGING.lock()) {
+                    Self::set_volume_from_column(area, mouse.column);
+                }
+            },
+            MouseEventKind::Up(MouseButton::Left) => *VOLUME_DRAGGING.lock() = false,
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                let up = mouse.kind == MouseEventKind::ScrollUp;
+                if targets.system_log.is_some_and(|area| area.contains(position)) {
+                    // The log scrolls back from the newest line, and only while focused.
+                    if navigation_state.region != InterfaceRegion::System {
+                        navigation_state.focus(InterfaceRegion::System);
                     }
+                    navigation_state.scroll = if up {
+                        navigation_state.scroll + WHEEL_LINES
+                    } else {
+                        navigation_state.scroll.saturating_sub(WHEEL_LINES)
+                    };
+                } else if targets.transcript.as_ref().is_some_and(|(area, _)| area.contains(position)) {
+                    navigation_state.scroll = if up {
+                        navigation_state.scroll.saturating_sub(WHEEL_LINES)
+                    } else {
+                        navigation_state.scroll + WHEEL_LINES
+                    };
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn set_volume_from_column(area: Rect, column: u16) {
+        let offset = column.saturating_sub(area.x).min(area.width.saturating_sub(1));
+        *VOLUME.lock() = offset as f32 / area.width.saturating_sub(1).max(1) as f32;
+    }
+
+    fn react_to_key_event(key: KeyEvent) {
+        if key.kind != event::KeyEventKind::Press {
+            return;
+        }
+        let mut navigation_state = NAVIGATION_STATE.lock();
+
+        if navigation_state.window == InterfaceWindow::History && KeyBinding::from_event(&key).is_typed() {
+            Self::react_to_search_key_event(&mut navigation_state, key);
+        } else if let Some(action) = key_map().action(&key, &navigation_state.key_scopes()) {
+            match action.scope() {
+                KeyScope::Global | KeyScope::Main => Self::react_to_action(&mut navigation_state, action),
+                KeyScope::Panel => {
+                    let region = navigation_state.region;
+                    region.react_to_action(&mut navigation_state, action);
                 },
-                _ => {},
+                KeyScope::Window | KeyScope::Library => Self::react_to_window_action(&mut navigation_state, action),
             }
         }
     }
 
+    fn react_to_action(navigation_state: &mut InterfaceNavigationState, action: Action) {
+        match action {
+            Action::Quit => *SIG_EXIT.lock() = true,
+            Action::Skip => *PLAYBACK_REQUEST.lock() = Some(PlaybackRequest::Skip),
+            Action::Hold => *SCANNER_REQUEST.lock() = Some(ScannerRequest::Hold),
+            Action::TuneUp => *SCANNER_REQUEST.lock() = Some(ScannerRequest::Tune(1)),
+            Action::TuneDown => *SCANNER_REQUEST.lock() = Some(ScannerRequest::Tune(-1)),
+            Action::RewindShort => *PLAYBACK_REQUEST.lock() = Some(PlaybackRequest::Rewind(10.0)),
+            Action::RewindLong => *PLAYBACK_REQUEST.lock() = Some(PlaybackRequest::Rewind(30.0)),
+            Action::Live => *PLAYBACK_REQUEST.lock() = Some(PlaybackRequest::Live),
+            Action::History => navigation_state.toggle_window(InterfaceWindow::History),
+            Action::Library => navigation_state.toggle_window(InterfaceWindow::Library),
+            Action::NextTheme => {
+                next_theme();
+            },
+            Action::Help => navigation_state.help = !navigation_state.help,
+            Action::Back => Self::go_back(navigation_state),
+            Action::FocusNext => navigation_state.focus_next(),
+            Action::FocusBand => navigation_state.focus(InterfaceRegion::Band),
+            Action::FocusSystem => navigation_state.focus(InterfaceRegion::System),
+            Action::FocusDirector => navigation_state.focus(InterfaceRegion::Director),
+            // Handled by the focused panel or the open window.
+            _ => {},
+        }
+    }
+
+    // Closes the help, then clears the transcript search, then leaves the focused panel or the
+    // open window.
+    fn go_back(navigation_state: &mut InterfaceNavigationState) {
+        if navigation_state.help {
+            navigation_state.help = false;
+            return;
+        }
+        match navigation_state.window {
+            InterfaceWindow::Main => navigation_state.focus(InterfaceRegion::None),
+            InterfaceWindow::History => {
+                let mut search = SEARCH_INPUT_BUFFER.lock();
+                if search.is_empty() {
+                    navigation_state.window = InterfaceWindow::Main;
+                } else {
+                    search.clear();
+                }
+                navigation_state.scroll = 0;
+            },
+            InterfaceWindow::Library => navigation_state.window = InterfaceWindow::Main,
+        }
+    }
+
+    // Typing searches the transcript.
+    fn react_to_search_key_event(navigation_state: &mut InterfaceNavigationState, key: KeyEvent) {
+        let mut search = SEARCH_INPUT_BUFFER.lock();
+        match key.code {
+            KeyCode::Backspace => {
+                search.pop();
+            },
+            KeyCode::Char(c) => search.push(c),
+            _ => return,
+        }
+        navigation_state.scroll = 0;
+    }
+
+    // Moves through the transcript by scrolling, and through the library by selecting a row,
+    // which can be cued on its own frequency or the current one.
+    fn react_to_window_action(navigation_state: &mut InterfaceNavigationState, action: Action) {
+        let position = match navigation_state.window {
+            InterfaceWindow::Library => &mut navigation_state.selected,
+            _ => &mut navigation_state.scroll,
+        };
+        match action {
+            Action::ListUp => *position = position.saturating_sub(1),
+            Action::ListDown => *position += 1,
+            Action::ListPageUp => *position = position.saturating_sub(PAGE_LINES),
+            Action::ListPageDown => *position += PAGE_LINES,
+            Action::ListTop => *position = 0,
+            Action::Cue | Action::CueHere => {
+                *CUE_REQUEST.lock() = Some(CueRequest {
+                    row: navigation_state.selected,
+                    on_current_frequency: action == Action::CueHere,
+                });
+            },
+            _ => {},
+        }
+    }
 }
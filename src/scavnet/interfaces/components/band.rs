@@ -0,0 +1,98 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    style::{Style, Stylize},
+    widgets::{Block, Padding, Paragraph},
+    Frame,
+};
+
+use crate::scavnet::interface::{clamp_selected, request_scanner, MOUSE_TARGETS, InterfaceNavigationState, ScannerRequest};
+use crate::scavnet::keys::{Action, KeyMap};
+use crate::scavnet::scanner::Scanner;
+use crate::scavnet::settings::is_debug;
+use crate::scavnet::theme::theme;
+
+const QUEUED_MARKER: &str = "◆";
+const CURSOR_MARKER: &str = "▲";
+
+pub fn key_hints(key_map: &KeyMap) -> String {
+    format!(
+        "{}/{} network, {} tune, {}",
+        key_map.hint(Action::PanelPrevious),
+        key_map.hint(Action::PanelNext),
+        key_map.hint(Action::PanelSelect),
+        key_map.hint(Action::Back),
+    )
+}
+
+/// Previous and next select a network, which select holds the scanner on.
+pub fn react_to_action(navigation_state: &mut InterfaceNavigationState, action: Action) {
+    match action {
+        Action::PanelPrevious => navigation_state.selected = navigation_state.selected.saturating_sub(1),
+        Action::PanelNext => navigation_state.selected += 1,
+        Action::PanelSelect => request_scanner(ScannerRequest::JumpToNetwork(navigation_state.selected)),
+        _ => {},
+    }
+}
+
+/// Draws the networks across the band, picking out the selected one while focused.
+pub fn render(frame: &mut Frame, scanner: &Scanner, focused: bool, block_default: Block, target_area: Rect) {
+    let theme = theme();
+    let block = block_default
+        .padding(Padding::horizontal(1))
+        .title_top(Line::from("BAND").fg(theme.title).bold().centered());
+    let inner_area = block.inner(target_area);
+    let width = inner_area.width as usize;
+    if width == 0 {
+        frame.render_widget(block, target_area);
+        return;
+    }
+    let networks = scanner.networks();
+    let selected_network = focused.then(|| clamp_selected(networks.networks.len().saturating_sub(1)));
+    let column = |position: f64| ((position * width as f64) as usize).min(width.saturating_sub(1));
+
+    let mut names = vec![Span::raw(" "); width];
+    let mut bars = vec![(String::from("━"), Style::new()); width];
+    let mut spans = vec![];
+    for (index, (name, start, end)) in networks.band_segments().iter().enumerate() {
+        let (start_column, end_column) = (column(*start), ((end * width as f64) as usize).min(width));
+        spans.push((start_column as u16, end_column as u16));
+        for bar in bars.iter_mut().take(end_column).skip(start_column) {
+            bar.1 = Style::new().fg(theme.band_color(index));
+        }
+        let name_style = if selected_network == Some(index) {
+            Style::new().fg(theme.highlight).bold().reversed()
+        } else {
+            Style::new()
+        };
+        for (offset, c) in name.to_uppercase().chars().take(end_column.saturating_sub(start_column + 1)).enumerate() {
+            names[start_column + offset] = Span::styled(c.to_string(), name_style);
+        }
+    }
+
+    MOUSE_TARGETS.lock().band = Some((inner_area, spans));
+
+    if is_debug() {
+        for frequency in scanner.queued_frequencies() {
+            if let Some(position) = networks.band_position(*frequency as u64) {
+                bars[column(position)] = (QUEUED_MARKER.to_string(), Style::new().fg(theme.alert));
+            }
+        }
+    }
+
+    let mut cursor = vec![Span::raw(" "); width];
+    if let Some(position) = networks.band_position(scanner.cur_freq() as u64) {
+        cursor[column(position)] = Span::styled(CURSOR_MARKER, Style::new().fg(theme.highlight).bold());
+    }
+
+    let band_text = vec![
+        Line::from(names).italic(),
+        Line::from(bars.into_iter().map(|(bar, style)| Span::styled(bar, style)).collect::<Vec<Span>>()),
+        Line::from(cursor),
+    ];
+
+    let band_para = Paragraph::new(band_text)
+        .block(block)
+        .style(Style::default().fg(theme.text));
+    frame.render_widget(band_para, target_area);
+}
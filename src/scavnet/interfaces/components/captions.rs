@@ -0,0 +1,25 @@
+use ratatui::{
+    layout::Rect,
+    text::Line,
+    style::{Style, Stylize},
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::scavnet::scanner::Scanner;
+use crate::scavnet::theme::theme;
+
+pub fn render(frame: &mut Frame, scanner: &Scanner, block_default: Block, target_area: Rect) {
+    let theme = theme();
+    let caption_text: Vec<Line> = scanner.caption()
+        .unwrap_or_default()
+        .lines()
+        .map(|line| Line::from(line.to_string()))
+        .collect();
+
+    let captions_para = Paragraph::new(caption_text)
+        .block(block_default.title_top(Line::from("CAPTIONS").fg(theme.title).bold().centered()))
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(captions_para, target_area);
+}
@@ -0,0 +1,100 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    style::{Style, Stylize},
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::scavnet::director::BuilderStatus;
+use crate::scavnet::interface::{clamp_scroll, InterfaceNavigationState};
+use crate::scavnet::keys::{Action, KeyMap};
+use crate::scavnet::system::System;
+use crate::scavnet::theme::theme;
+
+pub fn key_hints(key_map: &KeyMap) -> String {
+    format!(
+        "{}/{} scroll, {}",
+        key_map.hint(Action::PanelUp),
+        key_map.hint(Action::PanelDown),
+        key_map.hint(Action::Back),
+    )
+}
+
+pub fn react_to_action(navigation_state: &mut InterfaceNavigationState, action: Action) {
+    match action {
+        Action::PanelUp => navigation_state.scroll = navigation_state.scroll.saturating_sub(1),
+        Action::PanelDown => navigation_state.scroll += 1,
+        Action::PanelStart => navigation_state.scroll = 0,
+        _ => {},
+    }
+}
+
+/// Draws the director's state, scrolled while focused.
+pub fn render(frame: &mut Frame, system: &System, focused: bool, block_default: Block, target_area: Rect) {
+    let theme = theme();
+    let mut director_detail_text = vec![];
+
+    if let Some(inspection) = system.get_director_inspection() {
+        // Next queue
+        director_detail_text.push(
+            Line::from(vec![
+                Span::styled("Next queue", Style::new().italic()),
+                " : ".into(),
+                Span::raw(format!("{:.1}s", inspection.next_queue_in.as_secs_f32())),
+            ])
+        );
+
+        // Builder
+        let (builder_text, builder_color) = match &inspection.builder_status {
+            BuilderStatus::Idle => ("Idle".to_string(), theme.text),
+            BuilderStatus::Building => ("Building...".to_string(), theme.highlight),
+            BuilderStatus::Built(id) => (format!("Built {}", id), theme.good),
+            BuilderStatus::Failed(e) => (format!("Failed: {}", e), theme.alert),
+        };
+        director_detail_text.push(
+            Line::from(vec![
+                Span::styled("Builder", Style::new().italic()),
+                "    : ".into(),
+                Span::styled(builder_text, Style::new().fg(builder_color)),
+            ])
+        );
+
+        // Queue
+        director_detail_text.push(Line::from(Span::styled(format!("Queue ({})", inspection.queue.len()), Style::new().italic())));
+        for queued in &inspection.queue {
+            director_detail_text.push(
+                Line::from(format!(
+                    "  {:.5} MHz  {:>5.0}s  {} items  {}",
+                    queued.frequency as f32 / 1_000_000.0,
+                    queued.age.as_secs_f32(),
+                    queued.items,
+                    queued.id
+                ))
+            );
+        }
+
+        // Library selections
+        director_detail_text.push(Line::from(Span::styled("Selections", Style::new().italic())));
+        for selection in &inspection.selections {
+            let path = selection.path.iter()
+                .map(|(label, weight, chance)| format!("{} ({} / {:.0}%)", label, weight, chance * 100.0))
+                .collect::<Vec<String>>()
+                .join(" > ");
+            director_detail_text.push(
+                Line::from(vec![
+                    Span::styled(selection.selected_at.format("  %H:%M:%S ").to_string(), Style::new().fg(theme.muted)),
+                    Span::raw(format!("{} > {}", path, selection.conversation)),
+                ])
+            );
+        }
+    }
+
+    let scroll = if focused { clamp_scroll(director_detail_text.len().saturating_sub(1)) } else { 0 };
+    let director_para = Paragraph::new(director_detail_text)
+        .block(block_default.title_top(Line::from("DIRECTOR").fg(theme.title).bold().centered()))
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: false })
+        .scroll((scroll as u16, 0));
+    frame.render_widget(director_para, target_area);
+}
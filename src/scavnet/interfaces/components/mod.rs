@@ -1,4 +1,9 @@
+pub mod band;
+pub mod captions;
+pub mod readout;
+pub mod director;
 pub mod scanner;
 pub mod signal;
 pub mod system;
 pub mod titlebar;
+pub mod waterfall;
@@ -0,0 +1,124 @@
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    text::{Line, Span},
+    style::{Style, Stylize},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+use crate::scavnet::scanner::{Scanner, ScannerState};
+use crate::scavnet::theme::theme;
+
+const SEGMENT: char = '█';
+const LAMP: &str = "●";
+// Lines under the digits for the network and status lamp.
+const DETAIL_LINES: u16 = 3;
+
+// Segments lit for each digit, in the order top, upper right, lower right, bottom, lower left,
+// upper left, middle.
+const DIGIT_SEGMENTS: [[bool; 7]; 10] = [
+    [true, true, true, true, true, true, false],
+    [false, true, true, false, false, false, false],
+    [true, true, false, true, true, false, true],
+    [true, true, true, true, false, false, true],
+    [false, true, true, false, false, true, true],
+    [true, false, true, true, false, true, true],
+    [true, false, true, true, true, true, true],
+    [true, true, true, false, false, false, false],
+    [true, true, true, true, true, true, true],
+    [true, true, true, true, false, true, true],
+];
+
+/// The current frequency in digits as large as fit, with the network and a status lamp below.
+pub fn render(frame: &mut Frame, scanner: &Scanner, block_default: Block, target_area: Rect) {
+    let theme = theme();
+    let block = block_default.title_top(Line::from("FREQUENCY").fg(theme.title).bold().centered());
+    let inner_area = block.inner(target_area);
+    frame.render_widget(block, target_area);
+
+    let frequency_display = scanner.cur_freq_display();
+    let (digits, unit) = frequency_display.split_once(' ').unwrap_or((&frequency_display, ""));
+    let big_lines = big_digits(digits, inner_area.width, inner_area.height.saturating_sub(DETAIL_LINES));
+
+    let (status, lamp_color) = match scanner.state() {
+        ScannerState::Scanning => ("SCANNING", theme.muted),
+        ScannerState::Held => ("HOLDING", theme.highlight),
+        ScannerState::Replaying(_) => ("REPLAY", theme.highlight),
+        ScannerState::Idle => ("", theme.muted),
+        ScannerState::Receiving => ("RECEIVING", theme.good),
+    };
+
+    let mut readout_text: Vec<Line> = match big_lines {
+        Some(lines) => lines.into_iter()
+            .map(|line| Line::from(line).fg(theme.highlight))
+            .chain(std::iter::once(Line::from(unit.to_string()).fg(theme.muted)))
+            .collect(),
+        None => vec![Line::from(frequency_display.clone()).fg(theme.highlight).bold()],
+    };
+    readout_text.push(Line::from(scanner.cur_network_name().to_uppercase()).bold());
+    readout_text.push(Line::from(vec![
+        Span::styled(LAMP, Style::new().fg(lamp_color)),
+        Span::raw(format!(" {}", status)),
+    ]));
+
+    let [text_area] = Layout::vertical([Constraint::Length(readout_text.len() as u16)])
+        .flex(Flex::Center)
+        .areas(inner_area);
+    let readout_para = Paragraph::new(readout_text)
+        .style(Style::default().fg(theme.text))
+        .centered();
+    frame.render_widget(readout_para, text_area);
+}
+
+/// `digits` drawn with seven segments, as large as fit in `width` by `height`, or `None` if even
+/// the smallest don't.
+fn big_digits(digits: &str, width: u16, height: u16) -> Option<Vec<String>> {
+    // Digits are `size` square, which looks about twice as tall as wide in a terminal, with a
+    // column between characters; points take one column.
+    let count = digits.chars().filter(char::is_ascii_digit).count() as u16;
+    let points = digits.chars().count() as u16 - count;
+    let fits = |size: u16| count * (size + 1) + points * 2 <= width && size <= height;
+    let size = (1..=height / 2).rev().map(|half| half * 2 + 1).find(|size| fits(*size))?;
+
+    let middle = size / 2;
+    let lines = (0..size)
+        .map(|row| {
+            let mut line = String::new();
+            for c in digits.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    let [top, upper_right, lower_right, bottom, lower_left, upper_left, centre] = DIGIT_SEGMENTS[digit as usize];
+                    for column in 0..size {
+                        let horizontal = (row == 0 && top) || (row == middle && centre) || (row == size - 1 && bottom);
+                        let left = column == 0 && ((row <= middle && upper_left) || (row >= middle && lower_left));
+                        let right = column == size - 1 && ((row <= middle && upper_right) || (row >= middle && lower_right));
+                        line.push(if horizontal || left || right { SEGMENT } else { ' ' });
+                    }
+                    line.push(' ');
+                } else {
+                    line.push(if row == size - 1 { SEGMENT } else { ' ' });
+                    line.push(' ');
+                }
+            }
+            // Every line the same width, so they stay aligned when centred.
+            line.pop();
+            line
+        })
+        .collect();
+    Some(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_big_digits() {
+        assert_eq!(big_digits("1.7", 20, 3).unwrap(), [
+            "  █   ███",
+            "  █     █",
+            "  █ █   █",
+        ]);
+        assert_eq!(big_digits("1.7", 100, 5).unwrap()[0].chars().count(), 13);
+        assert_eq!(big_digits("123.45678", 4, 3), None);
+    }
+}
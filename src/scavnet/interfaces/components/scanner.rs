@@ -1,20 +1,26 @@
 use ratatui::{
     layout::Rect,
     text::{Line, Span},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     widgets::{Block, Paragraph},
     Frame,
 };
 
-use crate::scavnet::scanner::Scanner;
+use crate::scavnet::interface::{volume, MOUSE_TARGETS};
+use crate::scavnet::scanner::{Scanner, ScannerState};
+use crate::scavnet::theme::theme;
+
+const VOLUME_LABEL: &str = "Volume    : ";
+const VOLUME_BAR_WIDTH: u16 = 20;
 
 pub fn render(frame: &mut Frame, scanner: &Scanner, block_default: Block, target_area: Rect) {
+    let theme = theme();
     let mut scanner_detail_text = vec![];
-    let scanner_status_text = format!("{}", scanner.status());
-    let scanner_status_color = match scanner_status_text.as_str() {
-        "Scanning..." => Color::White,
-        "Paused" => Color::Yellow,
-        _ => Color::Green,
+    let scanner_status_text = scanner.status();
+    let scanner_status_color = match scanner.state() {
+        ScannerState::Scanning => theme.text,
+        ScannerState::Held => theme.highlight,
+        _ => theme.good,
     };
 
     // Status
@@ -57,8 +63,27 @@ pub fn render(frame: &mut Frame, scanner: &Scanner, block_default: Block, target
         ])
     );
 
+    // Volume, set by clicking or dragging the bar.
+    let block = block_default.clone().title_top(Line::from("SCANNER").fg(theme.title).bold().centered());
+    let inner_area = block.inner(target_area);
+    let bar_width = VOLUME_BAR_WIDTH.min(inner_area.width.saturating_sub(VOLUME_LABEL.len() as u16 + 5));
+    let filled = (volume() * bar_width as f32).round() as usize;
+    scanner_detail_text.push(
+        Line::from(vec![
+            Span::styled("Volume", Style::new().italic()),
+            "    : ".into(),
+            Span::styled("█".repeat(filled), Style::new().fg(theme.highlight)),
+            Span::styled("░".repeat(bar_width as usize - filled), Style::new().fg(theme.muted)),
+            Span::raw(format!(" {:.0}%", volume() * 100.0)),
+        ])
+    );
+    let volume_line = scanner_detail_text.len() as u16 - 1;
+    if bar_width > 0 && volume_line < inner_area.height {
+        MOUSE_TARGETS.lock().volume = Some(Rect::new(inner_area.x + VOLUME_LABEL.len() as u16, inner_area.y + volume_line, bar_width, 1));
+    }
+
     let scanner_para = Paragraph::new(scanner_detail_text)
-        .block(block_default.clone().title_top(Line::from("SCANNER").cyan().bold().centered()))
-        .style(Style::default().fg(Color::White));
+        .block(block)
+        .style(Style::default().fg(theme.text));
     frame.render_widget(scanner_para, target_area);
 }
\ No newline at end of file
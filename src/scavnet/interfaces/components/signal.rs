@@ -2,36 +2,50 @@ use ratatui::{
     layout::{Constraint, Rect},
     symbols::Marker,
     text::Line,
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     widgets::{Axis, Block, Chart, Dataset, GraphType},
     Frame
 };
 
+use crate::scavnet::bands::frequency_label;
 use crate::scavnet::scanner::Scanner;
+use crate::scavnet::theme::theme;
+
+const FREQUENCY_LABEL_COUNT: usize = 5;
 
 pub fn render(frame: &mut Frame, scanner: &Scanner, _block_default: Block, target_area: Rect) {
+    let theme = theme();
     let fftdata = scanner.get_fft_data();
     let mut fftchart = vec![];
     for (i, &val) in fftdata.iter().enumerate() {
         fftchart.push((i as f64, val as f64));
     }
+    let bands = scanner.spectrum_bands();
+    let band_count = bands.count();
+    let frequency_labels: Vec<Line> = (0..FREQUENCY_LABEL_COUNT)
+        .map(|i| {
+            let label = frequency_label(bands.frequency_at(i * band_count / (FREQUENCY_LABEL_COUNT - 1)));
+            if i % 2 == 0 { label.bold().into() } else { label.into() }
+        })
+        .collect();
+
     let dataset = Dataset::default()
         .marker(Marker::HalfBlock)
-        .style(Style::new().fg(Color::Blue))
+        .style(Style::new().fg(theme.spectrum))
         .graph_type(GraphType::Bar)
         .data(&fftchart);
 
     let fft_chart = Chart::new(vec![dataset])
-        .block(Block::bordered().title_top(Line::from("SIGNAL").cyan().bold().centered()))
+        .block(Block::bordered().border_style(Style::new().fg(theme.border)).title_top(Line::from("SIGNAL").fg(theme.title).bold().centered()))
         .x_axis(
             Axis::default()
-                .style(Style::default().gray())
-                .bounds([0.0, 256.0])
-                .labels(["500Hz".bold(), "1.5KHz".bold(), "2.5KHz".bold(), "3.5KHz".bold()]),
+                .style(Style::default().fg(theme.muted))
+                .bounds([0.0, band_count as f64])
+                .labels(frequency_labels),
         )
         .y_axis(
             Axis::default()
-                .style(Style::default().gray())
+                .style(Style::default().fg(theme.muted))
                 .bounds([0.0, 100.0])
                 .labels(["0".bold(), "50".into(), "100".bold()]),
         )
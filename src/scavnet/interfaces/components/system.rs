@@ -1,14 +1,45 @@
 use ratatui::{
     layout::Rect,
     text::{Line, Span},
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     widgets::{Block, Paragraph, Wrap},
     Frame,
 };
 
+use crate::scavnet::interface::{clamp_scroll, InterfaceNavigationState, MOUSE_TARGETS};
+use crate::scavnet::keys::{Action, KeyMap};
 use crate::scavnet::system::System;
+use crate::scavnet::theme::theme;
 
-pub fn render(frame: &mut Frame, system: &System, block_default: Block, target_area: Rect) {
+const PAGE_LINES: usize = 10;
+
+pub fn key_hints(key_map: &KeyMap) -> String {
+    format!(
+        "{}/{} scroll, {} latest, {}",
+        key_map.hint(Action::PanelUp),
+        key_map.hint(Action::PanelDown),
+        key_map.hint(Action::PanelEnd),
+        key_map.hint(Action::Back),
+    )
+}
+
+/// Scrolls back through the log, by lines from the newest.
+pub fn react_to_action(navigation_state: &mut InterfaceNavigationState, action: Action) {
+    match action {
+        Action::PanelUp => navigation_state.scroll += 1,
+        Action::PanelDown => navigation_state.scroll = navigation_state.scroll.saturating_sub(1),
+        Action::PanelPageUp => navigation_state.scroll += PAGE_LINES,
+        Action::PanelPageDown => navigation_state.scroll = navigation_state.scroll.saturating_sub(PAGE_LINES),
+        Action::PanelEnd => navigation_state.scroll = 0,
+        _ => {},
+    }
+}
+
+/// Draws the newest logs, or older ones while focused and scrolled back.
+pub fn render(frame: &mut Frame, system: &System, focused: bool, block_default: Block, target_area: Rect) {
+    let theme = theme();
+    let scroll = if focused { clamp_scroll(system.get_logs().len().saturating_sub(1)) } else { 0 };
+    MOUSE_TARGETS.lock().system_log = Some(target_area);
     let mut system_detail_text = vec![];
 
     let system_area_height = target_area.height;
@@ -19,8 +50,22 @@ pub fn render(frame: &mut Frame, system: &System, block_default: Block, target_a
             Span::styled(format!("Logs: {}", system_area_height), Style::new().italic()),
         ])
     );
+    if scroll > 0 {
+        system_detail_text.push(
+            Line::from(Span::styled(format!("{} newer lines below", scroll), Style::new().fg(theme.muted)))
+        );
+    }
+
+    if let Some(progress) = system.get_progress() {
+        system_detail_text.push(
+            Line::from(vec![
+                Span::styled(progress, Style::new().fg(theme.highlight)),
+            ])
+        );
+    }
 
-    let logs = system.get_last_x_logs(system_area_height as usize);
+    let mut logs = system.get_last_x_logs(system_area_height as usize + scroll);
+    logs.truncate(logs.len().saturating_sub(scroll));
     for log in logs {
         system_detail_text.push(
             Line::from(vec![
@@ -30,8 +75,8 @@ pub fn render(frame: &mut Frame, system: &System, block_default: Block, target_a
     }
 
     let mut system_para = Paragraph::new(system_detail_text)
-        .block(block_default.clone().title_top(Line::from("SYSTEM").cyan().bold().centered()))
-        .style(Style::default().fg(Color::White))
+        .block(block_default.clone().title_top(Line::from("SYSTEM").fg(theme.title).bold().centered()))
+        .style(Style::default().fg(theme.text))
         .wrap(Wrap { trim: true });
 
     let actual_line_count = system_para.line_count(system_area_width - 2);
@@ -1,5 +1,15 @@
-use ratatui::widgets::{Block, Borders};
+use ratatui::{
+    style::{Style, Stylize},
+    widgets::{Block, Borders},
+};
+
+use crate::scavnet::theme::theme;
 
 pub fn widget() -> Block<'static> {
-    Block::new().borders(Borders::TOP).title(format!("scavmainnet v0.1")).clone()
+    let theme = theme();
+    Block::new()
+        .borders(Borders::TOP)
+        .border_style(Style::new().fg(theme.border))
+        .title("scavmainnet v0.1")
+        .fg(theme.text)
 }
@@ -0,0 +1,53 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    style::{Style, Stylize},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+use crate::scavnet::scanner::Scanner;
+use crate::scavnet::theme::{theme, Theme};
+
+// Intensities from quiet to loud, shaded as well as colored so they show without color. Values
+// at or above WATERFALL_MAX are the loudest.
+const WATERFALL_SHADES: [&str; 5] = [" ", "░", "▒", "▓", "█"];
+const WATERFALL_MAX: f32 = 100.0;
+
+pub fn render(frame: &mut Frame, scanner: &Scanner, block_default: Block, target_area: Rect) {
+    let theme = theme();
+    let block = block_default.title_top(Line::from("WATERFALL").fg(theme.title).bold().centered());
+    let inner_area = block.inner(target_area);
+    let width = inner_area.width as usize;
+
+    let lines: Vec<Line> = scanner.fft_history()
+        .iter()
+        .take(inner_area.height as usize)
+        .map(|fftdata| {
+            Line::from(
+                (0..width)
+                    .map(|column| intensity_span(&theme, column_value(fftdata, column, width)))
+                    .collect::<Vec<Span>>()
+            )
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), target_area);
+}
+
+/// The loudest value among the bands that fall in `column`.
+fn column_value(fftdata: &[f32], column: usize, width: usize) -> f32 {
+    if fftdata.is_empty() {
+        return 0.0;
+    }
+    let first = column * fftdata.len() / width;
+    let last = ((column + 1) * fftdata.len() / width).max(first + 1).min(fftdata.len());
+    fftdata[first..last].iter().copied().fold(0.0, f32::max)
+}
+
+fn intensity_span(theme: &Theme, value: f32) -> Span<'static> {
+    let intensity = (value / WATERFALL_MAX).clamp(0.0, 1.0);
+    let shade = ((intensity * WATERFALL_SHADES.len() as f32) as usize).min(WATERFALL_SHADES.len() - 1);
+    let color = ((intensity * theme.waterfall.len() as f32) as usize).min(theme.waterfall.len() - 1);
+    Span::styled(WATERFALL_SHADES[shade], Style::new().fg(theme.waterfall[color]))
+}
@@ -0,0 +1,49 @@
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    text::{Line, Span},
+    style::{Style, Stylize},
+    widgets::{Clear, Paragraph},
+    Frame
+};
+
+use crate::scavnet::keys::{key_map, Action, KeyScope};
+use crate::scavnet::theme::theme;
+
+use super::super::interface::block_default;
+
+// Width of the key column.
+const KEYS_WIDTH: usize = 16;
+
+/// Draws the bindings of the actions in `scopes` over the middle of the screen.
+pub fn overlay(frame: &mut Frame, scopes: &[KeyScope]) {
+    let theme = theme();
+    let key_map = key_map();
+
+    let lines: Vec<Line> = Action::ALL.iter()
+        .filter(|action| scopes.contains(&action.scope()))
+        .map(|action| {
+            let keys = key_map.keys(*action)
+                .iter()
+                .map(|key| key.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            Line::from(vec![
+                Span::styled(format!("{:<width$}", keys, width = KEYS_WIDTH), Style::new().fg(theme.highlight)),
+                Span::raw(action.description()),
+            ])
+        })
+        .collect();
+
+    let area = centered(frame.area(), 72, lines.len() as u16 + 4);
+    let help_para = Paragraph::new(lines)
+        .block(block_default().title_top(Line::from("KEYS").fg(theme.title).bold().centered()))
+        .style(Style::new().fg(theme.text).bg(theme.background));
+    frame.render_widget(Clear, area);
+    frame.render_widget(help_para, area);
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+    area
+}
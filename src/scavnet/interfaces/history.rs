@@ -0,0 +1,96 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    text::{Line, Span},
+    style::{Style, Stylize},
+    widgets::Paragraph,
+    Frame
+};
+
+use crate::scavnet::interfaces::components::titlebar;
+use crate::scavnet::keys::{key_map, Action};
+use crate::scavnet::scanner::Scanner;
+use crate::scavnet::system::System;
+use crate::scavnet::theme::theme;
+
+use super::super::interface::{block_default, MOUSE_TARGETS, NAVIGATION_STATE, SEARCH_INPUT_BUFFER};
+
+pub fn ui(frame: &mut Frame, _scanner: &Scanner, system: &System) {
+    let theme = theme();
+    let block_default = block_default();
+    let search = SEARCH_INPUT_BUFFER.lock().clone();
+
+    let main_layout = Layout::new(
+        Direction::Vertical,
+        [
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ],
+    )
+    .split(frame.area());
+    frame.render_widget(titlebar::widget(), main_layout[0]);
+
+    // Search
+    let search_para = Paragraph::new(Line::from(vec![
+        Span::styled("Search: ", Style::new().italic()),
+        Span::raw(search.clone()),
+        Span::styled("_", Style::new().fg(theme.highlight)),
+    ]))
+    .block(block_default.clone().padding(Default::default()));
+    frame.render_widget(search_para, main_layout[1]);
+
+    // Transcript, newest first.
+    let entries = system.history().search(&search);
+    let mut transcript_text = vec![];
+    // The history entry each line belongs to, for clicks.
+    let mut line_entries = vec![];
+    for (index, entry) in entries.iter() {
+        transcript_text.push(
+            Line::from(vec![
+                Span::styled(entry.received_at.format("%H:%M:%S").to_string(), Style::new().fg(theme.muted)),
+                "  ".into(),
+                Span::styled(entry.frequency_display.clone(), Style::new().fg(theme.good)),
+                "  ".into(),
+                Span::raw(entry.network.to_uppercase()),
+                "  ".into(),
+                Span::styled(entry.conversation.clone(), Style::new().italic()),
+            ])
+        );
+        for caption_line in entry.captions.iter().flat_map(|caption| caption.lines()) {
+            transcript_text.push(Line::from(format!("    {}", caption_line)));
+        }
+        transcript_text.push(Line::default());
+        line_entries.resize(transcript_text.len(), Some(*index));
+    }
+
+    if system.history().is_empty() {
+        transcript_text.push(Line::from(Span::styled("No transmissions heard yet.", Style::new().italic())));
+    }
+
+    let transcript_area = main_layout[2];
+    let visible_lines = transcript_area.height.saturating_sub(4) as usize;
+    let scroll = {
+        let mut navigation_state = NAVIGATION_STATE.lock();
+        navigation_state.scroll = navigation_state.scroll.min(transcript_text.len().saturating_sub(visible_lines));
+        navigation_state.scroll
+    };
+
+    MOUSE_TARGETS.lock().transcript = Some((block_default.inner(transcript_area), line_entries.into_iter().skip(scroll).collect()));
+
+    let title = format!("TRANSCRIPT ({} of {})", entries.len(), system.history().len());
+    let key_map = key_map();
+    let help = format!(
+        " ({}/{}/{}/{}) scroll, (click) replay, ({}) clear search / back ",
+        key_map.hint(Action::ListUp),
+        key_map.hint(Action::ListDown),
+        key_map.hint(Action::ListPageUp),
+        key_map.hint(Action::ListPageDown),
+        key_map.hint(Action::Back),
+    );
+    let transcript_para = Paragraph::new(transcript_text)
+        .block(block_default.title_top(Line::from(title).fg(theme.title).bold().centered())
+            .title_bottom(Line::from(help).centered()))
+        .style(Style::default().fg(theme.text))
+        .scroll((scroll as u16, 0));
+    frame.render_widget(transcript_para, transcript_area);
+}
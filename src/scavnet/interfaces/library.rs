@@ -0,0 +1,74 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    text::{Line, Span},
+    style::{Style, Stylize},
+    widgets::{List, ListItem, ListState},
+    Frame
+};
+
+use crate::scavnet::interfaces::components::titlebar;
+use crate::scavnet::keys::{key_map, Action};
+use crate::scavnet::scanner::Scanner;
+use crate::scavnet::system::System;
+use crate::scavnet::theme::theme;
+use crate::scavnet::transmission::library::LibraryRowKind;
+
+use super::super::interface::{block_default, clamp_selected};
+
+pub fn ui(frame: &mut Frame, scanner: &Scanner, system: &System) {
+    let theme = theme();
+    let block_default = block_default();
+    let rows = system.get_library_rows();
+
+    let main_layout = Layout::new(
+        Direction::Vertical,
+        [
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ],
+    )
+    .split(frame.area());
+    frame.render_widget(titlebar::widget(), main_layout[0]);
+
+    let selected = clamp_selected(rows.len().saturating_sub(1));
+
+    let items: Vec<ListItem> = rows.iter()
+        .map(|row| {
+            let indent = "  ".repeat(row.depth);
+            let line = match &row.kind {
+                LibraryRowKind::Set => Line::from(vec![
+                    Span::raw(indent),
+                    Span::styled(row.label.clone(), Style::new().bold()),
+                    Span::styled(format!("  weight {}", row.weight), Style::new().fg(theme.muted)),
+                ]),
+                LibraryRowKind::Conversation { played, .. } => Line::from(vec![
+                    Span::raw(indent),
+                    Span::styled(if *played { "✓ " } else { "  " }, Style::new().fg(theme.good)),
+                    Span::raw(row.label.clone()),
+                    Span::styled(format!("  weight {}", row.weight), Style::new().fg(theme.muted)),
+                ]),
+                LibraryRowKind::Error(e) => Line::from(vec![
+                    Span::raw(indent),
+                    Span::styled(format!("{}: {}", row.label, e), Style::new().fg(theme.alert)),
+                ]),
+            };
+            ListItem::new(line)
+        })
+        .collect();
+
+    let key_map = key_map();
+    let help = format!(
+        " ({}) cue, ({}) cue on {}, ({}) back ",
+        key_map.hint(Action::Cue),
+        key_map.hint(Action::CueHere),
+        scanner.cur_freq_display(),
+        key_map.hint(Action::Back),
+    );
+    let library_list = List::new(items)
+        .block(block_default.title_top(Line::from("LIBRARY").fg(theme.title).bold().centered())
+            .title_bottom(Line::from(help).centered()))
+        .style(Style::default().fg(theme.text))
+        .highlight_style(Style::new().fg(theme.highlight).reversed());
+    let mut list_state = ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(library_list, main_layout[1], &mut list_state);
+}
@@ -1,27 +1,37 @@
 
 use ratatui::{
     layout::{Constraint, Direction, Layout},
+    text::Line,
+    style::{Style, Stylize},
+    widgets::Block,
     Frame
 };
 
+use crate::scavnet::interfaces::components::band;
+use crate::scavnet::interfaces::components::captions;
+use crate::scavnet::interfaces::components::readout;
+use crate::scavnet::interfaces::components::director;
 use crate::scavnet::interfaces::components::scanner;
 use crate::scavnet::interfaces::components::signal;
 use crate::scavnet::interfaces::components::system;
 use crate::scavnet::interfaces::components::titlebar;
+use crate::scavnet::interfaces::components::waterfall;
+use crate::scavnet::layout::{is_display_only, screen_layout, Panel};
 use crate::scavnet::scanner::Scanner;
+use crate::scavnet::bands::SpectrumDisplay;
+use crate::scavnet::settings::is_debug;
 use crate::scavnet::system::System;
 
 use super::super::{
     interface::{
-        InterfaceRegion,
-        InterfaceRegionState,
         InterfaceNavigationState,
+        InterfaceRegion,
     },
 };
 
 pub fn ui(frame: &mut Frame, scanner: &Scanner, system: &System) {
     let state = super::super::interface::NAVIGATION_STATE.lock().clone();
-    let block_default = super::super::interface::BLOCK_DEFAULT.clone();
+    let block_default = super::super::interface::block_default();
 
     // Main Layout
     let main_layout = Layout::new(
@@ -33,36 +43,58 @@ pub fn ui(frame: &mut Frame, scanner: &Scanner, system: &System) {
         ],
     )
     .split(frame.area());
-    frame.render_widget(
-        titlebar::widget(),
-        main_layout[0],
-    );
-
-    let vertical_layout = Layout::new(
-        Direction::Vertical,
-        [Constraint::Percentage(50), Constraint::Percentage(50)],
-    )
-    .split(main_layout[1]);
+    let body_area = if is_display_only() {
+        frame.area()
+    } else {
+        frame.render_widget(
+            titlebar::widget(),
+            main_layout[0],
+        );
+        main_layout[1]
+    };
 
-    let top_horiz_layout = Layout::new(
-        Direction::Horizontal,
-        [Constraint::Percentage(50), Constraint::Percentage(50)],
-    )
-    .split(vertical_layout[0]);
+    let debug = is_debug();
+    let panels = screen_layout(body_area.width, body_area.height)
+        .split(body_area, |panel| debug || panel != Panel::Director);
 
     // Widgets
-    scanner::render(frame, scanner, block_default.clone(), top_horiz_layout[0]);
-    system::render(frame, system, block_default.clone(), top_horiz_layout[1]);
-    signal::render(frame, scanner, block_default.clone(), vertical_layout[1]);
-
-    // Region based comps.
-    let mut loop1_title = format!(" [Loop1] {:.2} BPM | ", "");
-    match state.region {
-        InterfaceRegion::Loop1 => {
-            loop1_title += "(a) add, (ESC) exit ";
-        }
-        _ => {
-            loop1_title += "(F1) select ";
+    for (panel, area) in panels {
+        match panel {
+            Panel::Band => band::render(frame, scanner, state.region == InterfaceRegion::Band, region_block(&state, InterfaceRegion::Band, &block_default), area),
+            Panel::Scanner => scanner::render(frame, scanner, block_default.clone(), area),
+            Panel::System => system::render(frame, system, state.region == InterfaceRegion::System, region_block(&state, InterfaceRegion::System, &block_default), area),
+            Panel::Director => director::render(frame, system, state.region == InterfaceRegion::Director, region_block(&state, InterfaceRegion::Director, &block_default), area),
+            Panel::Captions => captions::render(frame, scanner, block_default.clone(), area),
+            Panel::Readout => readout::render(frame, scanner, block_default.clone(), area),
+            Panel::Signal => signal::render(frame, scanner, block_default.clone(), area),
+            Panel::Waterfall => waterfall::render(frame, scanner, block_default.clone(), area),
+            Panel::Spectrum => match scanner.spectrum_display() {
+                SpectrumDisplay::Bars => signal::render(frame, scanner, block_default.clone(), area),
+                SpectrumDisplay::Waterfall => waterfall::render(frame, scanner, block_default.clone(), area),
+                SpectrumDisplay::Both => {
+                    let spectrum_layout = Layout::new(
+                        Direction::Vertical,
+                        [Constraint::Percentage(50), Constraint::Percentage(50)],
+                    )
+                    .split(area);
+                    signal::render(frame, scanner, block_default.clone(), spectrum_layout[0]);
+                    waterfall::render(frame, scanner, block_default.clone(), spectrum_layout[1]);
+                }
+            },
         }
     }
 }
+
+// A focusable panel's block, in the region color, with the key that focuses it or, while focused,
+// its keys on the bottom border.
+fn region_block(state: &InterfaceNavigationState, region: InterfaceRegion, block_default: &Block<'static>) -> Block<'static> {
+    let color = state.get_region_color(region);
+    let hint = if state.region == region {
+        format!(" {} ", region.key_hints())
+    } else {
+        format!(" ({}) select ", region.focus_key())
+    };
+    block_default.clone()
+        .border_style(Style::new().fg(color))
+        .title_bottom(Line::from(hint).fg(color).right_aligned())
+}
@@ -1,3 +1,7 @@
 pub mod components;
+pub mod help;
+pub mod history;
+pub mod library;
 pub mod main;
+pub mod plain;
 
@@ -0,0 +1,63 @@
+/// Something worth a line of output when the UI isn't drawn.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlainEvent {
+    SignalDetected { frequency: String, network: String, transmission: String },
+    ItemPlaying { frequency: String, item: String, caption: String },
+    SignalLost { frequency: String },
+}
+
+impl PlainEvent {
+    /// The event as `time event key="value" ...`, so each line can be split on spaces outside
+    /// quotes.
+    pub fn line(&self, time: &str) -> String {
+        let (name, fields) = match self {
+            PlainEvent::SignalDetected { frequency, network, transmission } => {
+                ("signal", vec![("frequency", frequency), ("network", network), ("transmission", transmission)])
+            }
+            PlainEvent::ItemPlaying { frequency, item, caption } => {
+                ("playing", vec![("frequency", frequency), ("item", item), ("caption", caption)])
+            }
+            PlainEvent::SignalLost { frequency } => ("lost", vec![("frequency", frequency)]),
+        };
+
+        let mut line = format!("{} {}", time, name);
+        for (key, value) in fields {
+            line.push_str(&format!(" {}=\"{}\"", key, quoted(value)));
+        }
+        line
+    }
+}
+
+// Escapes a value so it stays inside its quotes and on one line.
+fn quoted(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_event_line() {
+        let detected = PlainEvent::SignalDetected {
+            frequency: "146.520 MHz".to_string(),
+            network: "Harbor".to_string(),
+            transmission: "t1".to_string(),
+        };
+        assert_eq!(
+            detected.line("2024-01-01T00:00:00"),
+            "2024-01-01T00:00:00 signal frequency=\"146.520 MHz\" network=\"Harbor\" transmission=\"t1\""
+        );
+
+        let playing = PlainEvent::ItemPlaying {
+            frequency: "146.520 MHz".to_string(),
+            item: "a".to_string(),
+            caption: "Say \"over\"\nthen wait.".to_string(),
+        };
+        assert!(playing.line("t").ends_with("caption=\"Say \\\"over\\\"\\nthen wait.\""));
+    }
+}
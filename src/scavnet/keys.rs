@@ -0,0 +1,540 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+lazy_static! {
+    static ref KEY_MAP: Mutex<KeyMap> = Mutex::new(KeyMap::default());
+}
+
+/// Where an action's keys are listened for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyScope {
+    /// Every window.
+    Global,
+    /// The main window.
+    Main,
+    /// The focused panel of the main window, which uses the actions it has a use for.
+    Panel,
+    /// The transcript and library windows.
+    Window,
+    /// The library window.
+    Library,
+}
+
+impl KeyScope {
+    /// The scopes listened to together, by window and whether a panel is focused.
+    const ACTIVE_TOGETHER: [&'static [KeyScope]; 4] = [
+        &[KeyScope::Global, KeyScope::Main],
+        &[KeyScope::Global, KeyScope::Main, KeyScope::Panel],
+        &[KeyScope::Global, KeyScope::Window],
+        &[KeyScope::Global, KeyScope::Window, KeyScope::Library],
+    ];
+
+    fn overlaps(&self, other: KeyScope) -> bool {
+        Self::ACTIVE_TOGETHER.iter().any(|scopes| scopes.contains(self) && scopes.contains(&other))
+    }
+}
+
+/// Something the operator can do.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Action {
+    Quit,
+    Skip,
+    Hold,
+    TuneUp,
+    TuneDown,
+    RewindShort,
+    RewindLong,
+    Live,
+    History,
+    Library,
+    NextTheme,
+    Help,
+    Back,
+    FocusNext,
+    FocusBand,
+    FocusSystem,
+    FocusDirector,
+    PanelUp,
+    PanelDown,
+    PanelPageUp,
+    PanelPageDown,
+    PanelStart,
+    PanelEnd,
+    PanelPrevious,
+    PanelNext,
+    PanelSelect,
+    ListUp,
+    ListDown,
+    ListPageUp,
+    ListPageDown,
+    ListTop,
+    Cue,
+    CueHere,
+}
+
+impl Action {
+    pub const ALL: [Action; 33] = [
+        Action::Quit,
+        Action::Skip,
+        Action::Hold,
+        Action::TuneUp,
+        Action::TuneDown,
+        Action::RewindShort,
+        Action::RewindLong,
+        Action::Live,
+        Action::History,
+        Action::Library,
+        Action::NextTheme,
+        Action::Help,
+        Action::Back,
+        Action::FocusNext,
+        Action::FocusBand,
+        Action::FocusSystem,
+        Action::FocusDirector,
+        Action::PanelUp,
+        Action::PanelDown,
+        Action::PanelPageUp,
+        Action::PanelPageDown,
+        Action::PanelStart,
+        Action::PanelEnd,
+        Action::PanelPrevious,
+        Action::PanelNext,
+        Action::PanelSelect,
+        Action::ListUp,
+        Action::ListDown,
+        Action::ListPageUp,
+        Action::ListPageDown,
+        Action::ListTop,
+        Action::Cue,
+        Action::CueHere,
+    ];
+
+    pub fn scope(&self) -> KeyScope {
+        match self {
+            Action::Quit
+            | Action::History
+            | Action::Library
+            | Action::NextTheme
+            | Action::Help
+            | Action::Back => KeyScope::Global,
+            Action::PanelUp
+            | Action::PanelDown
+            | Action::PanelPageUp
+            | Action::PanelPageDown
+            | Action::PanelStart
+            | Action::PanelEnd
+            | Action::PanelPrevious
+            | Action::PanelNext
+            | Action::PanelSelect => KeyScope::Panel,
+            Action::ListUp
+            | Action::ListDown
+            | Action::ListPageUp
+            | Action::ListPageDown
+            | Action::ListTop => KeyScope::Window,
+            Action::Cue | Action::CueHere => KeyScope::Library,
+            _ => KeyScope::Main,
+        }
+    }
+
+    /// The name used for the action in the `[keys]` settings.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Skip => "skip",
+            Action::Hold => "hold",
+            Action::TuneUp => "tune_up",
+            Action::TuneDown => "tune_down",
+            Action::RewindShort => "rewind_short",
+            Action::RewindLong => "rewind_long",
+            Action::Live => "live",
+            Action::History => "history",
+            Action::Library => "library",
+            Action::NextTheme => "next_theme",
+            Action::Help => "help",
+            Action::Back => "back",
+            Action::FocusNext => "focus_next",
+            Action::FocusBand => "focus_band",
+            Action::FocusSystem => "focus_system",
+            Action::FocusDirector => "focus_director",
+            Action::PanelUp => "panel_up",
+            Action::PanelDown => "panel_down",
+            Action::PanelPageUp => "panel_page_up",
+            Action::PanelPageDown => "panel_page_down",
+            Action::PanelStart => "panel_start",
+            Action::PanelEnd => "panel_end",
+            Action::PanelPrevious => "panel_previous",
+            Action::PanelNext => "panel_next",
+            Action::PanelSelect => "panel_select",
+            Action::ListUp => "list_up",
+            Action::ListDown => "list_down",
+            Action::ListPageUp => "list_page_up",
+            Action::ListPageDown => "list_page_down",
+            Action::ListTop => "list_top",
+            Action::Cue => "cue",
+            Action::CueHere => "cue_here",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Skip => "Skip the transmission playing",
+            Action::Hold => "Hold on, or resume scanning from, this frequency",
+            Action::TuneUp => "Tune up a step while holding",
+            Action::TuneDown => "Tune down a step while holding",
+            Action::RewindShort => "Replay the last 10 seconds",
+            Action::RewindLong => "Replay the last 30 seconds",
+            Action::Live => "Stop replaying",
+            Action::History => "Open or close the transcript",
+            Action::Library => "Open or close the library browser",
+            Action::NextTheme => "Switch to the next theme",
+            Action::Help => "Show or hide this help",
+            Action::Back => "Close the help, clear the search, leave the panel or window",
+            Action::FocusNext => "Focus the next panel",
+            Action::FocusBand => "Focus the BAND panel",
+            Action::FocusSystem => "Focus the SYSTEM panel",
+            Action::FocusDirector => "Focus the DIRECTOR panel",
+            Action::PanelUp => "Scroll the focused panel up a line",
+            Action::PanelDown => "Scroll the focused panel down a line",
+            Action::PanelPageUp => "Scroll the focused panel up a page",
+            Action::PanelPageDown => "Scroll the focused panel down a page",
+            Action::PanelStart => "Scroll the focused panel to its start",
+            Action::PanelEnd => "Scroll the focused panel to its end",
+            Action::PanelPrevious => "Select the previous item in the focused panel",
+            Action::PanelNext => "Select the next item in the focused panel",
+            Action::PanelSelect => "Use the item selected in the focused panel",
+            Action::ListUp => "Move up a line",
+            Action::ListDown => "Move down a line",
+            Action::ListPageUp => "Move up a page",
+            Action::ListPageDown => "Move down a page",
+            Action::ListTop => "Move to the top",
+            Action::Cue => "Cue the selected conversation on its own frequency",
+            Action::CueHere => "Cue the selected conversation on the current frequency",
+        }
+    }
+
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "Ctrl+c"],
+            Action::Skip => &["s"],
+            Action::Hold => &["p", "Space"],
+            Action::TuneUp => &["Up"],
+            Action::TuneDown => &["Down"],
+            Action::RewindShort => &["r"],
+            Action::RewindLong => &["R"],
+            Action::Live => &["l"],
+            Action::History => &["h"],
+            Action::Library => &["b"],
+            Action::NextTheme => &["t"],
+            Action::Help => &["?", "F1"],
+            Action::Back => &["Esc"],
+            Action::FocusNext => &["Tab"],
+            Action::FocusBand => &["1"],
+            Action::FocusSystem => &["2"],
+            Action::FocusDirector => &["3"],
+            // Up and Down tune, so panels scroll by line with k and j.
+            Action::PanelUp => &["k"],
+            Action::PanelDown => &["j"],
+            Action::PanelPageUp => &["PageUp"],
+            Action::PanelPageDown => &["PageDown"],
+            Action::PanelStart => &["Home"],
+            Action::PanelEnd => &["End"],
+            Action::PanelPrevious => &["Left"],
+            Action::PanelNext => &["Right"],
+            Action::PanelSelect => &["Enter"],
+            Action::ListUp => &["Up"],
+            Action::ListDown => &["Down"],
+            Action::ListPageUp => &["PageUp"],
+            Action::ListPageDown => &["PageDown"],
+            Action::ListTop => &["Home"],
+            Action::Cue => &["Enter"],
+            Action::CueHere => &["c"],
+        }
+    }
+}
+
+/// A key with its modifiers, written like `q`, `Ctrl+c`, `PageUp` or `F1`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut parts: Vec<&str> = spec.split('+').collect();
+        // A lone "+" is the plus key.
+        if spec.ends_with("++") || spec == "+" {
+            parts.retain(|part| !part.is_empty());
+            parts.push("+");
+        }
+        let key = parts.pop().filter(|key| !key.is_empty()).ok_or(format!("Invalid key '{}'.", spec))?;
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in parts {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("Invalid modifier '{}' in key '{}'.", modifier, spec)),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "esc" => KeyCode::Esc,
+                "enter" => KeyCode::Enter,
+                "space" => KeyCode::Char(' '),
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                function if function.starts_with('f') => function[1..].parse::<u8>()
+                    .ok()
+                    .filter(|number| (1..=12).contains(number))
+                    .map(KeyCode::F)
+                    .ok_or(format!("Invalid key '{}'.", spec))?,
+                _ => return Err(format!("Invalid key '{}'.", spec)),
+            },
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+
+    // Shift is part of the character typed, so it is left out when matching characters.
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Whether the key edits the transcript search: a character without Ctrl or Alt, or
+    /// Backspace.
+    pub fn is_typed(&self) -> bool {
+        match self.code {
+            KeyCode::Char(_) => !self.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT),
+            KeyCode::Backspace => true,
+            _ => false,
+        }
+    }
+
+    // Keys typed into the transcript search never reach the actions listened for there. Global
+    // actions may still use characters, which work in every other window.
+    fn is_reserved_for(&self, action: Action) -> bool {
+        match action.scope() {
+            KeyScope::Window => self.is_typed(),
+            KeyScope::Global => self.code == KeyCode::Backspace,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(number) => write!(f, "F{}", number),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// Which action each key triggers.
+#[derive(Clone, Debug)]
+pub struct KeyMap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::from_overrides(&HashMap::new()).expect("Default key bindings conflict.")
+    }
+}
+
+impl KeyMap {
+    /// The default bindings, with those of each action named in `overrides` replaced. Returns
+    /// every unknown action, invalid key, key reserved for the transcript search and key bound to
+    /// more than one action listened for at the same time.
+    pub fn from_overrides(overrides: &HashMap<String, Vec<String>>) -> Result<Self, Vec<String>> {
+        let mut errors = Vec::new();
+        for name in overrides.keys() {
+            if !Action::ALL.iter().any(|action| action.name() == name) {
+                errors.push(format!("Unknown key binding action '{}'.", name));
+            }
+        }
+
+        let mut bindings: Vec<(KeyBinding, Action)> = Vec::new();
+        for action in Action::ALL {
+            let keys: Vec<String> = match overrides.get(action.name()) {
+                Some(keys) => keys.clone(),
+                None => action.default_keys().iter().map(|key| key.to_string()).collect(),
+            };
+            for key in keys {
+                match KeyBinding::parse(&key) {
+                    Ok(binding) if binding.is_reserved_for(action) => {
+                        errors.push(format!("Key '{}' of '{}' is typed into the transcript search.", binding, action.name()));
+                    }
+                    Ok(binding) => {
+                        if let Some((_, other)) = bindings.iter()
+                            .find(|(bound, other)| *bound == binding && other.scope().overlaps(action.scope())) {
+                            errors.push(format!("Key '{}' is bound to both '{}' and '{}'.", binding, other.name(), action.name()));
+                        } else {
+                            bindings.push((binding, action));
+                        }
+                    }
+                    Err(e) => errors.push(e),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self { bindings })
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The action `key` triggers among those of `scopes`.
+    pub fn action(&self, key: &KeyEvent, scopes: &[KeyScope]) -> Option<Action> {
+        let binding = KeyBinding::from_event(key);
+        self.bindings.iter()
+            .find(|(bound, action)| *bound == binding && scopes.contains(&action.scope()))
+            .map(|(_, action)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> Vec<KeyBinding> {
+        self.bindings.iter().filter(|(_, bound)| *bound == action).map(|(binding, _)| *binding).collect()
+    }
+
+    /// The first key of `action`, for hints, or "-" if it has none.
+    pub fn hint(&self, action: Action) -> String {
+        self.keys(action).first().map(|key| key.to_string()).unwrap_or("-".to_string())
+    }
+}
+
+/// Applies `overrides` to the default bindings. If they are invalid the defaults are kept, and
+/// the problems returned.
+pub fn init_key_map(overrides: &HashMap<String, Vec<String>>) -> Vec<String> {
+    match KeyMap::from_overrides(overrides) {
+        Ok(key_map) => {
+            *KEY_MAP.lock() = key_map;
+            Vec::new()
+        }
+        Err(errors) => errors.into_iter()
+            .chain(std::iter::once("Using the default key bindings.".to_string()))
+            .collect(),
+    }
+}
+
+pub fn key_map() -> KeyMap {
+    KEY_MAP.lock().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_binding() {
+        assert_eq!(KeyBinding::parse("q").unwrap(), KeyBinding::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("Ctrl+c").unwrap(), KeyBinding::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert_eq!(KeyBinding::parse("PageDown").unwrap(), KeyBinding::new(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("F5").unwrap(), KeyBinding::new(KeyCode::F(5), KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("Alt++").unwrap(), KeyBinding::new(KeyCode::Char('+'), KeyModifiers::ALT));
+        assert!(KeyBinding::parse("Hyper+x").is_err());
+        assert!(KeyBinding::parse("F13").is_err());
+        assert!(KeyBinding::parse("").is_err());
+    }
+
+    #[test]
+    fn test_key_binding_display_round_trip() {
+        for spec in ["q", "Ctrl+c", "Space", "F1", "PageUp", "Up"] {
+            assert_eq!(KeyBinding::parse(spec).unwrap().to_string(), spec);
+        }
+    }
+
+    #[test]
+    fn test_key_map_overrides() {
+        let overrides = HashMap::from([("quit".to_string(), vec!["x".to_string(), "Ctrl+c".to_string()])]);
+        let key_map = KeyMap::from_overrides(&overrides).unwrap();
+
+        assert_eq!(key_map.action(&KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE), &[KeyScope::Global, KeyScope::Main]), Some(Action::Quit));
+        assert_eq!(key_map.action(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE), &[KeyScope::Global, KeyScope::Main]), None);
+        assert_eq!(key_map.action(&KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT), &[KeyScope::Global, KeyScope::Main]), Some(Action::RewindLong));
+    }
+
+    #[test]
+    fn test_key_map_panel_scope() {
+        let key_map = KeyMap::default();
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert_eq!(key_map.action(&enter, &[KeyScope::Global, KeyScope::Main]), None);
+        assert_eq!(key_map.action(&enter, &[KeyScope::Global, KeyScope::Main, KeyScope::Panel]), Some(Action::PanelSelect));
+        assert_eq!(key_map.action(&enter, &[KeyScope::Global, KeyScope::Window, KeyScope::Library]), Some(Action::Cue));
+    }
+
+    #[test]
+    fn test_key_map_reserved_keys() {
+        let overrides = HashMap::from([
+            ("list_up".to_string(), vec!["k".to_string()]),
+            ("back".to_string(), vec!["Esc".to_string(), "Backspace".to_string()]),
+            ("list_down".to_string(), vec!["Ctrl+n".to_string()]),
+        ]);
+        let errors = KeyMap::from_overrides(&overrides).unwrap_err();
+
+        assert_eq!(errors, [
+            "Key 'Backspace' of 'back' is typed into the transcript search.",
+            "Key 'k' of 'list_up' is typed into the transcript search.",
+        ]);
+    }
+
+    #[test]
+    fn test_key_map_conflicts() {
+        let overrides = HashMap::from([
+            ("skip".to_string(), vec!["q".to_string()]),
+            ("jump".to_string(), vec!["j".to_string()]),
+        ]);
+        let errors = KeyMap::from_overrides(&overrides).unwrap_err();
+
+        assert_eq!(errors, [
+            "Unknown key binding action 'jump'.",
+            "Key 'q' is bound to both 'quit' and 'skip'.",
+        ]);
+
+        // Panel keys work alongside the main window's, so can't share them.
+        let overrides = HashMap::from([("panel_up".to_string(), vec!["Up".to_string()])]);
+        let errors = KeyMap::from_overrides(&overrides).unwrap_err();
+
+        assert_eq!(errors, ["Key 'Up' is bound to both 'tune_up' and 'panel_up'."]);
+    }
+}
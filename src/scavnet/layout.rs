@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+pub const LAYOUT_AUTO: &str = "auto";
+pub const LAYOUT_COMPACT: &str = "compact";
+pub const LAYOUT_STANDARD: &str = "standard";
+pub const LAYOUT_WIDE: &str = "wide";
+pub const LAYOUT_DISPLAY: &str = "display";
+
+// Terminals narrower or shorter than these get the compact layout, and those at least
+// WIDE_MIN_WIDTH wide the wide one.
+const COMPACT_MAX_WIDTH: u16 = 100;
+const COMPACT_MAX_HEIGHT: u16 = 32;
+const WIDE_MIN_WIDTH: u16 = 180;
+
+// The band needs 5 lines: borders plus names, bars and cursor.
+const DEFAULT_COMPACT: &str = "band 5 | scanner system 9 | captions 4 | spectrum *";
+const DEFAULT_STANDARD: &str = "band 5 | scanner system director 45% | captions 6 | spectrum *";
+const DEFAULT_WIDE: &str = "band 5 | scanner:2 captions:3 system:3 director:2 40% | signal waterfall *";
+const DEFAULT_DISPLAY: &str = "readout *";
+
+lazy_static! {
+    static ref LAYOUTS: Mutex<Layouts> = Mutex::new(Layouts::default());
+}
+
+/// A panel of the main window.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Panel {
+    Band,
+    Scanner,
+    System,
+    Director,
+    Captions,
+    /// The frequency in large digits.
+    Readout,
+    /// The signal, waterfall or both, as set by `spectrum.display`.
+    Spectrum,
+    Signal,
+    Waterfall,
+}
+
+impl Panel {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "band" => Some(Panel::Band),
+            "scanner" => Some(Panel::Scanner),
+            "system" => Some(Panel::System),
+            "director" => Some(Panel::Director),
+            "captions" => Some(Panel::Captions),
+            "readout" => Some(Panel::Readout),
+            "spectrum" => Some(Panel::Spectrum),
+            "signal" => Some(Panel::Signal),
+            "waterfall" => Some(Panel::Waterfall),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RowHeight {
+    Lines(u16),
+    Percent(u16),
+    /// What is left once the other rows are placed, shared between such rows.
+    Fill,
+}
+
+/// Panels side by side, with their relative widths.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutRow {
+    pub panels: Vec<(Panel, u16)>,
+    pub height: RowHeight,
+}
+
+/// Rows of panels from top to bottom, written like `band 5 | scanner:2 system 45% | spectrum *`:
+/// rows are separated by `|`, and each lists its panels, optionally with a width weight, then its
+/// height in lines, as a percentage, or `*` to fill.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScreenLayout {
+    pub rows: Vec<LayoutRow>,
+}
+
+impl ScreenLayout {
+    pub fn parse(description: &str) -> Result<Self, String> {
+        let mut rows = Vec::new();
+        for row in description.split('|') {
+            let mut words: Vec<&str> = row.split_whitespace().collect();
+            let height = words.pop().ok_or(format!("Empty row in layout '{}'.", description))?;
+            let height = if height == "*" {
+                RowHeight::Fill
+            } else if let Some(percent) = height.strip_suffix('%') {
+                RowHeight::Percent(percent.parse().map_err(|_| format!("Invalid row height '{}'.", height))?)
+            } else {
+                RowHeight::Lines(height.parse().map_err(|_| format!("Invalid row height '{}', or it is missing.", height))?)
+            };
+            if words.is_empty() {
+                return Err(format!("No panels in layout row '{}'.", row.trim()));
+            }
+
+            let mut panels = Vec::new();
+            for word in words {
+                let (name, weight) = word.split_once(':').unwrap_or((word, "1"));
+                let panel = Panel::from_name(name).ok_or(format!("Unknown panel '{}'.", name))?;
+                let weight = weight.parse().map_err(|_| format!("Invalid width '{}' for panel '{}'.", weight, name))?;
+                panels.push((panel, weight));
+            }
+            rows.push(LayoutRow { panels, height });
+        }
+        Ok(Self { rows })
+    }
+
+    /// Where each panel goes in `area`, leaving out those `available` rejects and any rows left
+    /// empty.
+    pub fn split(&self, area: Rect, available: impl Fn(Panel) -> bool) -> Vec<(Panel, Rect)> {
+        let rows: Vec<(Vec<(Panel, u16)>, RowHeight)> = self.rows.iter()
+            .map(|row| (row.panels.iter().copied().filter(|(panel, _)| available(*panel)).collect::<Vec<_>>(), row.height))
+            .filter(|(panels, _)| !panels.is_empty())
+            .collect();
+
+        let row_constraints: Vec<Constraint> = rows.iter()
+            .map(|(_, height)| match height {
+                RowHeight::Lines(lines) => Constraint::Length(*lines),
+                RowHeight::Percent(percent) => Constraint::Percentage(*percent),
+                RowHeight::Fill => Constraint::Fill(1),
+            })
+            .collect();
+        let row_areas = Layout::new(Direction::Vertical, row_constraints).split(area);
+
+        rows.iter()
+            .zip(row_areas.iter())
+            .flat_map(|((panels, _), row_area)| {
+                let panel_areas = Layout::new(Direction::Horizontal, panels.iter().map(|(_, weight)| Constraint::Fill(*weight)))
+                    .split(*row_area);
+                panels.iter().map(|(panel, _)| *panel).zip(panel_areas.iter().copied()).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+struct Layouts {
+    mode: String,
+    compact: ScreenLayout,
+    standard: ScreenLayout,
+    wide: ScreenLayout,
+    /// Shown full screen, without the title bar.
+    display: ScreenLayout,
+}
+
+impl Default for Layouts {
+    fn default() -> Self {
+        Self {
+            mode: LAYOUT_AUTO.to_string(),
+            compact: ScreenLayout::parse(DEFAULT_COMPACT).unwrap(),
+            standard: ScreenLayout::parse(DEFAULT_STANDARD).unwrap(),
+            wide: ScreenLayout::parse(DEFAULT_WIDE).unwrap(),
+            display: ScreenLayout::parse(DEFAULT_DISPLAY).unwrap(),
+        }
+    }
+}
+
+/// The layout named `mode` for a terminal of the given size, or in `auto` mode the one that
+/// suits it.
+fn layout_name(mode: &str, width: u16, height: u16) -> &str {
+    match mode {
+        LAYOUT_AUTO if width < COMPACT_MAX_WIDTH || height < COMPACT_MAX_HEIGHT => LAYOUT_COMPACT,
+        LAYOUT_AUTO if width >= WIDE_MIN_WIDTH => LAYOUT_WIDE,
+        LAYOUT_AUTO => LAYOUT_STANDARD,
+        mode => mode,
+    }
+}
+
+/// Sets the layout mode and replaces the default descriptions with those in `descriptions`, by
+/// layout name. Returns a message for each that can't be used.
+pub fn init_layouts(mode: &str, descriptions: &HashMap<String, String>) -> Vec<String> {
+    let mut layouts = Layouts::default();
+    let mut errors = Vec::new();
+
+    for (name, description) in descriptions {
+        let layout = match name.as_str() {
+            LAYOUT_COMPACT => &mut layouts.compact,
+            LAYOUT_STANDARD => &mut layouts.standard,
+            LAYOUT_WIDE => &mut layouts.wide,
+            LAYOUT_DISPLAY => &mut layouts.display,
+            _ => {
+                errors.push(format!("Unknown layout '{}'.", name));
+                continue;
+            }
+        };
+        match ScreenLayout::parse(description) {
+            Ok(parsed) => *layout = parsed,
+            Err(e) => errors.push(format!("Layout '{}' not used: {}", name, e)),
+        }
+    }
+
+    if [LAYOUT_AUTO, LAYOUT_COMPACT, LAYOUT_STANDARD, LAYOUT_WIDE, LAYOUT_DISPLAY].contains(&mode) {
+        layouts.mode = mode.to_string();
+    } else {
+        errors.push(format!("Layout mode '{}' not found, using '{}'.", mode, LAYOUT_AUTO));
+    }
+    *LAYOUTS.lock() = layouts;
+    errors
+}
+
+/// The layout to draw the main window with in an area of the given size.
+pub fn screen_layout(width: u16, height: u16) -> ScreenLayout {
+    let layouts = LAYOUTS.lock();
+    match layout_name(&layouts.mode, width, height) {
+        LAYOUT_COMPACT => layouts.compact.clone(),
+        LAYOUT_WIDE => layouts.wide.clone(),
+        LAYOUT_DISPLAY => layouts.display.clone(),
+        _ => layouts.standard.clone(),
+    }
+}
+
+/// Whether the display layout is in use, which takes the whole screen.
+pub fn is_display_only() -> bool {
+    LAYOUTS.lock().mode == LAYOUT_DISPLAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_layout() {
+        let layout = ScreenLayout::parse("band 5 | scanner:2 system 45% | spectrum *").unwrap();
+
+        assert_eq!(layout.rows.len(), 3);
+        assert_eq!(layout.rows[0], LayoutRow { panels: vec![(Panel::Band, 1)], height: RowHeight::Lines(5) });
+        assert_eq!(layout.rows[1].panels, [(Panel::Scanner, 2), (Panel::System, 1)]);
+        assert_eq!(layout.rows[1].height, RowHeight::Percent(45));
+        assert_eq!(layout.rows[2].height, RowHeight::Fill);
+    }
+
+    #[test]
+    fn test_parse_layout_invalid() {
+        assert!(ScreenLayout::parse("band").is_err());
+        assert!(ScreenLayout::parse("band 5 | 6").is_err());
+        assert!(ScreenLayout::parse("radar 5").is_err());
+        assert!(ScreenLayout::parse("band:x 5").is_err());
+        assert!(ScreenLayout::parse("band 5 ||").is_err());
+    }
+
+    #[test]
+    fn test_split_layout() {
+        let layout = ScreenLayout::parse(DEFAULT_STANDARD).unwrap();
+        let areas = layout.split(Rect::new(0, 0, 100, 50), |panel| panel != Panel::Director);
+        let panels: Vec<Panel> = areas.iter().map(|(panel, _)| *panel).collect();
+
+        assert_eq!(panels, [Panel::Band, Panel::Scanner, Panel::System, Panel::Captions, Panel::Spectrum]);
+        assert_eq!(areas[0].1, Rect::new(0, 0, 100, 5));
+        assert_eq!(areas[1].1.width + areas[2].1.width, 100);
+        assert_eq!(areas[4].1.bottom(), 50);
+    }
+
+    #[test]
+    fn test_layout_name() {
+        assert_eq!(layout_name(LAYOUT_AUTO, 80, 24), LAYOUT_COMPACT);
+        assert_eq!(layout_name(LAYOUT_AUTO, 120, 40), LAYOUT_STANDARD);
+        assert_eq!(layout_name(LAYOUT_AUTO, 240, 60), LAYOUT_WIDE);
+        assert_eq!(layout_name(LAYOUT_WIDE, 80, 24), LAYOUT_WIDE);
+    }
+}
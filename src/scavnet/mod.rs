@@ -1,11 +1,25 @@
+pub mod analysis;
+pub mod archive;
+pub mod audio;
+pub mod bands;
+pub mod cli;
+pub mod commands;
 pub mod core;
 pub mod director;
 pub mod interface;
 pub mod interfaces;
+pub mod keys;
+pub mod layout;
 pub mod fft;
+pub mod history;
+pub mod input;
 pub mod networks;
 pub mod scanner;
 pub mod settings;
 pub mod system;
+#[cfg(test)]
+pub mod testing;
+pub mod theme;
 pub mod time;
+pub mod web;
 pub mod transmission;
@@ -104,6 +104,41 @@ impl RadioNetworks {
         }
     }
 
+    /// Where the network at `index` starts in the scan frequencies.
+    pub fn network_scan_index(&self, index: usize) -> Option<usize> {
+        if index >= self.networks.len() {
+            return None;
+        }
+        Some(self.networks[..index].iter()
+            .map(|network| (network.end_freq - network.start_freq).div_ceil(network.step) as usize)
+            .sum())
+    }
+
+    /// Each network's share of the band overview, as start and end fractions of the sum of
+    /// every network's range, in the order they are configured.
+    pub fn band_segments(&self) -> Vec<(String, f64, f64)> {
+        let total_range: u64 = self.networks.iter().map(|network| network.end_freq - network.start_freq).sum();
+        let mut segments = Vec::new();
+        let mut cumulative_range = 0;
+        for network in &self.networks {
+            let start = cumulative_range as f64 / total_range as f64;
+            cumulative_range += network.end_freq - network.start_freq;
+            segments.push((network.name.clone(), start, cumulative_range as f64 / total_range as f64));
+        }
+        segments
+    }
+
+    /// Where `freq` falls on the band overview, from 0 to 1, if it is in a network.
+    pub fn band_position(&self, freq: u64) -> Option<f64> {
+        self.networks.iter()
+            .zip(self.band_segments())
+            .find(|(network, _)| freq >= network.start_freq && freq <= network.end_freq)
+            .map(|(network, (_, start, end))| {
+                let offset = (freq - network.start_freq) as f64 / (network.end_freq - network.start_freq) as f64;
+                start + (end - start) * offset
+            })
+    }
+
     pub fn scan_frequencies(&self) -> Vec<u32> {
         self.scan_frequencies.clone()
     }
@@ -152,6 +187,43 @@ mod tests {
         assert_eq!(networks.scan_frequencies.len(), 55300000);
     }
 
+    #[tokio::test]
+    async fn test_network_scan_index() {
+        let networks = get_testing_data().await;
+        let scavnet_index = networks.network_scan_index(1).unwrap();
+
+        assert_eq!(networks.network_scan_index(0), Some(0));
+        assert_eq!(networks.scan_frequencies[scavnet_index] as u64, networks.networks[1].start_freq);
+        assert_eq!(networks.network_scan_index(2), None);
+    }
+
+    #[tokio::test]
+    async fn test_network_scan_index_partial_step() {
+        let mut networks = get_raw_testing_data().await;
+        (networks.networks[0].start_freq, networks.networks[0].end_freq) = (1000, 1025);
+        (networks.networks[1].start_freq, networks.networks[1].end_freq) = (2000, 2030);
+        networks.build_scan_frequencies();
+        let scavnet_index = networks.network_scan_index(1).unwrap();
+
+        assert_eq!(scavnet_index, 3);
+        assert_eq!(networks.scan_frequencies[scavnet_index] as u64, networks.networks[1].start_freq);
+    }
+
+    #[tokio::test]
+    async fn test_band_position() {
+        let networks = get_raw_testing_data().await;
+        let segments = networks.band_segments();
+        let (pubnet_start, pubnet_end) = (networks.networks[0].start_freq, networks.networks[0].end_freq);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].1, 0.0);
+        assert_eq!(segments[0].2, segments[1].1);
+        assert_eq!(segments[1].2, 1.0);
+        assert_eq!(networks.band_position(pubnet_start), Some(0.0));
+        assert_eq!(networks.band_position(pubnet_end), Some(segments[0].2));
+        assert_eq!(networks.band_position(10000), None);
+    }
+
     #[tokio::test]
     async fn test_no_name() {
         let mut networks = get_raw_testing_data().await;
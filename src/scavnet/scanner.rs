@@ -1,41 +1,78 @@
-use std::iter::Cycle;
-use std::vec::IntoIter;
+use std::collections::VecDeque;
+use std::sync::Arc;
 
+use crate::scavnet::audio::rewind::RewindBuffer;
+use crate::scavnet::bands::{BandScale, SpectrumBands, SpectrumDisplay};
 use crate::scavnet::networks::RadioNetworks;
 
+// Spectrum frames kept for the waterfall, newest first.
+const FFT_HISTORY_LENGTH: usize = 256;
+
+/// What the scanner is doing, from which its status text is derived.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScannerState {
+    Idle,
+    Scanning,
+    Held,
+    Receiving,
+    // Seconds back from live.
+    Replaying(f32),
+}
+
 #[derive(Clone)]
 pub struct Scanner {
     networks: RadioNetworks,
     cur_frequency: u32,
-    freq_iter: Cycle<IntoIter<u32>>,
+    scan_frequencies: Vec<u32>,
+    // Where the scan carries on from.
+    scan_index: usize,
     scanning: bool,
+    held: bool,
+    skipping: bool,
     fftdata: Vec<f32>,
-    status: String,
+    fft_history: VecDeque<Vec<f32>>,
+    bands: SpectrumBands,
+    spectrum_display: SpectrumDisplay,
+    state: ScannerState,
+    caption: Option<String>,
+    queued_frequencies: Vec<u32>,
     noise_profile: Vec<Vec<f32>>,
     noise_index: usize,
+    rewind: Option<Arc<RewindBuffer>>,
+    replay_resume: Option<(bool, ScannerState)>,
 }
 
+#[allow(dead_code)]
 impl Scanner {
     pub fn empty() -> Self {
         Self {
             networks: RadioNetworks::empty(),
             cur_frequency: 0,
-            freq_iter: vec![].into_iter().cycle(),
+            scan_frequencies: vec![],
+            scan_index: 0,
             scanning: false,
+            held: false,
+            skipping: false,
             fftdata: vec![],
-            status: String::new(),
+            fft_history: VecDeque::with_capacity(FFT_HISTORY_LENGTH),
+            bands: SpectrumBands::new(BandScale::Log, 0.0, 0.0, 1),
+            spectrum_display: SpectrumDisplay::from_settings(),
+            state: ScannerState::Idle,
+            caption: None,
+            queued_frequencies: vec![],
             noise_profile: vec![],
             noise_index: 0,
+            rewind: None,
+            replay_resume: None,
         }
     }
 
-    pub fn new(networks: RadioNetworks) -> Self {
+    pub fn new(networks: RadioNetworks, bands: SpectrumBands, spectrum_display: SpectrumDisplay) -> Self {
         let scan_frequencies = networks.scan_frequencies();
         let cur_frequency = scan_frequencies.first().cloned().unwrap_or(0);
-        let freq_iter = scan_frequencies.into_iter().cycle();
         let noise_profile: Vec<Vec<f32>> = (0..256)
             .map(|_| {
-                (0..=256)
+                (0..bands.count())
                     .map(|_| rand::random::<u8>() as f32 / 50.0)
                     .collect()
             })
@@ -43,22 +80,32 @@ impl Scanner {
         Self {
             networks,
             cur_frequency,
-            freq_iter,
+            scan_frequencies,
+            scan_index: 0,
             scanning: false,
+            held: false,
+            skipping: false,
             fftdata: vec![],
-            status: String::new(),
+            fft_history: VecDeque::with_capacity(FFT_HISTORY_LENGTH),
+            bands,
+            spectrum_display,
+            state: ScannerState::Idle,
+            caption: None,
+            queued_frequencies: vec![],
             noise_profile,
             noise_index: 0,
+            rewind: None,
+            replay_resume: None,
         }
     }
 
-    pub fn _get_networks(&self) -> RadioNetworks {
-        self.networks.clone()
+    pub fn networks(&self) -> &RadioNetworks {
+        &self.networks
     }
 
     pub fn start(&mut self) {
         self.scanning = true;
-        self.status = "Scanning...".to_string();
+        self.state = ScannerState::Scanning;
     }
 
     pub fn pause(&mut self) {
@@ -67,37 +114,162 @@ impl Scanner {
 
     pub fn pause_for_playback(&mut self) {
         self.pause();
-        self.status = "Recieving transmission...".to_string();
+        self.state = ScannerState::Receiving;
     }
 
     pub fn resume_after_playback(&mut self) {
-        self.start();
+        self.caption = None;
+        self.skipping = false;
+        if self.held {
+            self.hold();
+        } else {
+            self.start();
+        }
+    }
+
+    /// Stops scanning to listen on the current frequency, or resumes scanning from it.
+    pub fn toggle_hold(&mut self) {
+        if self.held {
+            self.held = false;
+            self.start();
+        } else {
+            self.hold();
+        }
+    }
+
+    fn hold(&mut self) {
+        self.held = true;
+        self.pause();
+        self.state = ScannerState::Held;
+    }
+
+    /// Moves `steps` scan frequencies up or down while held.
+    pub fn tune_step(&mut self, steps: i64) {
+        if !self.held || self.scan_frequencies.is_empty() {
+            return;
+        }
+        let count = self.scan_frequencies.len() as i64;
+        let index = (self.scan_index as i64 - 1 + steps).rem_euclid(count) as usize;
+        self.cur_frequency = self.scan_frequencies[index];
+        self.scan_index = (index + 1) % self.scan_frequencies.len();
+    }
+
+    /// Holds on the first frequency of the network at `index`, to scan on from there when
+    /// resumed.
+    pub fn jump_to_network(&mut self, index: usize) {
+        let Some(scan_index) = self.networks.network_scan_index(index) else {
+            return;
+        };
+        let Some(frequency) = self.scan_frequencies.get(scan_index) else {
+            return;
+        };
+        self.cur_frequency = *frequency;
+        self.scan_index = (scan_index + 1) % self.scan_frequencies.len();
+        if !self.held {
+            self.hold();
+        }
     }
 
+    /// The next frequency to listen on: the next scan frequency, the current one while held, or
+    /// `None` while paused.
     pub fn next_freq(&mut self) -> Option<u32> {
-        if (!self.scanning) {
+        if self.held {
+            return Some(self.cur_frequency);
+        }
+        if !self.scanning || self.scan_frequencies.is_empty() {
             return None;
         }
-        self.cur_frequency = self.freq_iter.next().unwrap_or(0);
+        self.cur_frequency = self.scan_frequencies[self.scan_index];
+        self.scan_index = (self.scan_index + 1) % self.scan_frequencies.len();
         Some(self.cur_frequency)
     }
 
+    /// Marks the transmission playing to be cut short.
+    pub fn skip_transmission(&mut self) {
+        self.skipping = true;
+    }
+
+    pub fn is_skipping(&self) -> bool {
+        self.skipping
+    }
+
+    /// Jumps to `frequency`, leaving the scan to carry on from where it was.
+    pub fn tune(&mut self, frequency: u32) {
+        self.cur_frequency = frequency;
+    }
+
+    pub fn cur_freq(&self) -> u32 {
+        self.cur_frequency
+    }
+
     pub fn cur_freq_display(&self) -> String {
-        format!("{:.5} MHz", self.cur_frequency as f32 / 1_000_000.0)
+        frequency_display(self.cur_frequency)
+    }
+
+    /// Records displayed spectrum frames in `rewind`, so they can be shown again on replay.
+    pub fn attach_rewind_buffer(&mut self, rewind: Arc<RewindBuffer>) {
+        self.rewind = Some(rewind);
     }
 
     pub fn update_fft_data(&mut self, fftdata: Vec<f32>) {
+        if let Some(rewind) = &self.rewind {
+            rewind.record_fft_frame(fftdata.clone());
+        }
+        self.set_fft_data(fftdata);
+    }
+
+    pub fn replay_fft_data(&mut self, fftdata: Vec<f32>) {
+        self.set_fft_data(fftdata);
+    }
+
+    fn set_fft_data(&mut self, fftdata: Vec<f32>) {
+        if self.fft_history.len() == FFT_HISTORY_LENGTH {
+            self.fft_history.pop_back();
+        }
+        self.fft_history.push_front(fftdata.clone());
         self.fftdata = fftdata;
     }
 
+    pub fn begin_replay(&mut self, secs: f32) {
+        if self.replay_resume.is_none() {
+            self.replay_resume = Some((self.scanning, self.state));
+        }
+        self.pause();
+        self.state = ScannerState::Replaying(secs);
+    }
+
+    pub fn end_replay(&mut self) {
+        if let Some((scanning, state)) = self.replay_resume.take() {
+            self.scanning = scanning;
+            self.state = state;
+        }
+    }
+
     pub fn get_fft_data(&self) -> Vec<f32> {
         self.fftdata.clone()
     }
 
+    /// Recent spectrum frames, newest first.
+    pub fn fft_history(&self) -> &VecDeque<Vec<f32>> {
+        &self.fft_history
+    }
+
+    pub fn spectrum_bands(&self) -> &SpectrumBands {
+        &self.bands
+    }
+
+    pub fn spectrum_display(&self) -> SpectrumDisplay {
+        self.spectrum_display
+    }
+
     pub fn is_scanning(&self) -> bool {
         self.scanning
     }
 
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
     pub fn simulate_noise(&mut self) {
         let data = &self.noise_profile[self.noise_index];
         self.update_fft_data(data.clone());
@@ -110,12 +282,45 @@ impl Scanner {
         self.noise_index = (self.noise_index + 1) % self.noise_profile.len();
     }
 
+    pub fn set_caption(&mut self, caption: Option<String>) {
+        self.caption = caption;
+    }
+
+    pub fn caption(&self) -> Option<String> {
+        self.caption.clone()
+    }
+
+    /// Frequencies with transmissions waiting for the scanner, shown on the band overview in
+    /// debug mode.
+    pub fn set_queued_frequencies(&mut self, frequencies: Vec<u32>) {
+        self.queued_frequencies = frequencies;
+    }
+
+    pub fn queued_frequencies(&self) -> &[u32] {
+        &self.queued_frequencies
+    }
+
     pub fn status(&self) -> String {
-        self.status.clone()
+        match self.state {
+            ScannerState::Idle => String::new(),
+            ScannerState::Scanning => "Scanning...".to_string(),
+            ScannerState::Held => "Paused".to_string(),
+            ScannerState::Receiving => "Recieving transmission...".to_string(),
+            ScannerState::Replaying(secs) => format!("Replaying -{}s...", secs),
+        }
+    }
+
+    pub fn state(&self) -> ScannerState {
+        self.state
     }
 
     pub fn cur_network_name(&self) -> String {
         self.networks.network_name_from_freq(self.cur_frequency as u64).unwrap_or("Unknown".to_string().to_uppercase())
     }
 
-}
\ No newline at end of file
+}
+
+/// A frequency in Hz as shown to the listener.
+pub fn frequency_display(frequency: u32) -> String {
+    format!("{:.5} MHz", frequency as f32 / 1_000_000.0)
+}
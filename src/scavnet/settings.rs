@@ -1,9 +1,20 @@
+use std::collections::HashMap;
+use std::error::Error;
+
 use config::Config;
 
+use crate::scavnet::audio::spectrum::{SPECTRUM_SOURCE_LIVE, SPECTRUM_SOURCE_PRECOMPUTED};
+use crate::scavnet::bands::{BAND_SCALE_LOG, SPECTRUM_DISPLAY_BARS};
+use crate::scavnet::layout::{LAYOUT_AUTO, LAYOUT_COMPACT, LAYOUT_DISPLAY, LAYOUT_STANDARD, LAYOUT_WIDE};
+use crate::scavnet::theme::THEME_DEFAULT;
+
 use super::super::SETTINGS;
 
+pub const SETTINGS_PATH: &str = "Settings";
 const SCREEN_REDRAW_RATE: u128 = 288;
+const MOUSE_ENABLED: bool = true;
 const DATA_DIR: &str = "data";
+const CACHE_DIR: &str = "cache";
 const DEBUG_STATUS: bool = false;
 const NETWORK_LIBRARY_PATH: &str = "networks.yaml";
 const TRANSMISSION_LIBRARY_PATH: &str = "transmissions/sets.yaml";
@@ -15,9 +26,37 @@ const HISS_POSTROLL_MIN_TIME: f32 = 0.5;
 const HISS_POSTROLL_MAX_TIME: f32 = 2.0;
 const TRANSMISSION_SINK_VOLUME: f32 = 1.0;
 const HISS_SINK_VOLUME: f32 = 0.3;
+const AUDIO_OUTPUT: &str = "device";
+const AUDIO_OUTPUT_DEVICE: &str = "default";
+const AUDIO_SAMPLE_RATE: u32 = 44100;
+const AUDIO_CHANNELS: u16 = 2;
+const AUDIO_WAV_DIR: &str = "recordings";
+const AUDIO_WAV_SEGMENT_TIME: u64 = 3600;
+const AUDIO_PCM_PATH: &str = "-";
+const REWIND_BUFFER_TIME: u64 = 120;
+// The buffer is allocated up front, so keep it to an hour.
+const REWIND_BUFFER_TIME_MAX: u64 = 3600;
+const SPECTRUM_SOURCE: &str = SPECTRUM_SOURCE_LIVE;
+const SPECTRUM_DISPLAY: &str = SPECTRUM_DISPLAY_BARS;
+const SPECTRUM_SCALE: &str = BAND_SCALE_LOG;
+const SPECTRUM_MIN_FREQ: f32 = 100.0;
+const SPECTRUM_MAX_FREQ: f32 = 5000.0;
+const SPECTRUM_BANDS: usize = 128;
+const SPECTRUM_BANDS_MAX: usize = 1024;
+const THEME_NAME: &str = THEME_DEFAULT;
+const THEME_DIR: &str = "themes";
+const ARCHIVE_ENABLED: bool = false;
+const ARCHIVE_DIR: &str = "archive";
+const ARCHIVE_MAX_ENTRIES: usize = 1000;
+const ARCHIVE_MAX_AGE_DAYS: u64 = 7;
+const WEB_ENABLED: bool = false;
+const WEB_BIND: &str = "127.0.0.1";
+const WEB_PORT: u16 = 8080;
 
-pub fn init_settings() -> (u128, bool) {
-    load_settings();
+/// Loads the settings file at `config_path`, with or without its extension. `data_dir` and
+/// `debug` take precedence over the file's values when given.
+pub fn init_settings(config_path: &str, data_dir: Option<String>, debug: bool) -> Result<(u128, bool), Box<dyn Error>> {
+    load_settings(config_path, data_dir, debug)?;
 
     let screen_redraw_rate = SETTINGS.lock()
         .get_int("performance.screen_redraw_rate")
@@ -27,14 +66,20 @@ pub fn init_settings() -> (u128, bool) {
         .get_bool("debug")
         .unwrap_or(DEBUG_STATUS);
 
-    (screen_redraw_rate, debug)
+    Ok((screen_redraw_rate, debug))
 }
 
-pub fn load_settings() {
-    *SETTINGS.lock() = Config::builder()
-        .add_source(config::File::with_name("Settings"))
-        .build()
-        .unwrap();
+pub fn load_settings(config_path: &str, data_dir: Option<String>, debug: bool) -> Result<(), Box<dyn Error>> {
+    let mut builder = Config::builder()
+        .add_source(config::File::with_name(config_path));
+    if let Some(data_dir) = data_dir {
+        builder = builder.set_override("paths.data_dir", data_dir)?;
+    }
+    if debug {
+        builder = builder.set_override("debug", true)?;
+    }
+    *SETTINGS.lock() = builder.build()?;
+    Ok(())
 }
 
 pub fn network_library_path() -> String {
@@ -61,6 +106,16 @@ pub fn get_data_dir() -> String {
         .unwrap_or(DATA_DIR.to_string())
 }
 
+pub fn get_cache_dir() -> String {
+    SETTINGS.lock()
+        .get_string("paths.cache_dir")
+        .unwrap_or(CACHE_DIR.to_string())
+}
+
+pub fn transmission_data_dir() -> String {
+    format!("{}/transmissions", get_data_dir())
+}
+
 pub fn is_debug() -> bool {
     SETTINGS.lock()
         .get_bool("debug")
@@ -105,4 +160,174 @@ pub fn get_volumes() -> (f32, f32) {
         .get_float("volumes.hiss_whitenoise_sink_volume")
         .unwrap_or(HISS_SINK_VOLUME as f64) as f32;
     (transmission_sink_volume, hiss_sink_volume)
-}
\ No newline at end of file
+}
+
+pub fn get_audio_output_device() -> String {
+    SETTINGS.lock()
+        .get_string("audio.output_device")
+        .unwrap_or(AUDIO_OUTPUT_DEVICE.to_string())
+}
+
+pub fn get_audio_output() -> String {
+    SETTINGS.lock()
+        .get_string("audio.output")
+        .unwrap_or(AUDIO_OUTPUT.to_string())
+}
+
+pub fn get_audio_format() -> Result<(u32, u16), Box<dyn Error>> {
+    let sample_rate = SETTINGS.lock()
+        .get_int("audio.sample_rate")
+        .unwrap_or(AUDIO_SAMPLE_RATE as i64);
+    let channels = SETTINGS.lock()
+        .get_int("audio.channels")
+        .unwrap_or(AUDIO_CHANNELS as i64);
+    let sample_rate = u32::try_from(sample_rate).ok().filter(|&rate| rate > 0)
+        .ok_or(format!("Invalid audio sample rate: {}", sample_rate))?;
+    let channels = u16::try_from(channels).ok().filter(|&channels| channels > 0)
+        .ok_or(format!("Invalid audio channel count: {}", channels))?;
+    Ok((sample_rate, channels))
+}
+
+pub fn get_audio_wav_settings() -> Result<(String, u64), Box<dyn Error>> {
+    let wav_dir = SETTINGS.lock()
+        .get_string("audio.wav_dir")
+        .unwrap_or(AUDIO_WAV_DIR.to_string());
+    let segment_time = SETTINGS.lock()
+        .get_int("audio.wav_segment_time")
+        .unwrap_or(AUDIO_WAV_SEGMENT_TIME as i64);
+    let segment_time = u64::try_from(segment_time).ok().filter(|&secs| secs > 0)
+        .ok_or(format!("Invalid audio wav_segment_time: {}", segment_time))?;
+    Ok((wav_dir, segment_time))
+}
+
+pub fn get_audio_pcm_path() -> String {
+    SETTINGS.lock()
+        .get_string("audio.pcm_path")
+        .unwrap_or(AUDIO_PCM_PATH.to_string())
+}
+
+pub fn get_archive_settings() -> Result<(bool, String, usize, u64), Box<dyn Error>> {
+    let enabled = SETTINGS.lock()
+        .get_bool("archive.enabled")
+        .unwrap_or(ARCHIVE_ENABLED);
+    let dir = SETTINGS.lock()
+        .get_string("archive.dir")
+        .unwrap_or(ARCHIVE_DIR.to_string());
+    let max_entries = SETTINGS.lock()
+        .get_int("archive.max_entries")
+        .unwrap_or(ARCHIVE_MAX_ENTRIES as i64);
+    let max_age_days = SETTINGS.lock()
+        .get_int("archive.max_age_days")
+        .unwrap_or(ARCHIVE_MAX_AGE_DAYS as i64);
+    let max_entries = usize::try_from(max_entries).ok()
+        .ok_or(format!("Invalid archive max_entries: {}", max_entries))?;
+    let max_age_days = u64::try_from(max_age_days).ok()
+        .ok_or(format!("Invalid archive max_age_days: {}", max_age_days))?;
+    Ok((enabled, dir, max_entries, max_age_days))
+}
+
+pub fn get_rewind_buffer_time() -> Result<u64, Box<dyn Error>> {
+    let buffer_time = SETTINGS.lock()
+        .get_int("rewind.buffer_time")
+        .unwrap_or(REWIND_BUFFER_TIME as i64);
+    let buffer_time = u64::try_from(buffer_time).ok().filter(|&secs| secs <= REWIND_BUFFER_TIME_MAX)
+        .ok_or(format!("Invalid rewind buffer_time: {} (0 to {} seconds)", buffer_time, REWIND_BUFFER_TIME_MAX))?;
+    Ok(buffer_time)
+}
+
+pub fn get_spectrum_source() -> String {
+    SETTINGS.lock()
+        .get_string("spectrum.source")
+        .unwrap_or(SPECTRUM_SOURCE.to_string())
+}
+
+pub fn uses_precomputed_spectrum() -> bool {
+    get_spectrum_source() == SPECTRUM_SOURCE_PRECOMPUTED
+}
+
+pub fn get_spectrum_display() -> String {
+    SETTINGS.lock()
+        .get_string("spectrum.display")
+        .unwrap_or(SPECTRUM_DISPLAY.to_string())
+}
+
+pub fn get_spectrum_band_settings() -> Result<(String, f32, f32, usize), Box<dyn Error>> {
+    let scale = SETTINGS.lock()
+        .get_string("spectrum.scale")
+        .unwrap_or(SPECTRUM_SCALE.to_string());
+    let min_freq = SETTINGS.lock()
+        .get_float("spectrum.min_freq")
+        .unwrap_or(SPECTRUM_MIN_FREQ as f64) as f32;
+    let max_freq = SETTINGS.lock()
+        .get_float("spectrum.max_freq")
+        .unwrap_or(SPECTRUM_MAX_FREQ as f64) as f32;
+    let bands = SETTINGS.lock()
+        .get_int("spectrum.bands")
+        .unwrap_or(SPECTRUM_BANDS as i64);
+    let bands = usize::try_from(bands).ok().filter(|&bands| (1..=SPECTRUM_BANDS_MAX).contains(&bands))
+        .ok_or(format!("Invalid spectrum bands: {} (1 to {})", bands, SPECTRUM_BANDS_MAX))?;
+    Ok((scale, min_freq, max_freq, bands))
+}
+
+pub fn get_theme_settings() -> (String, String) {
+    let name = SETTINGS.lock()
+        .get_string("theme.name")
+        .unwrap_or(THEME_NAME.to_string());
+    let dir = SETTINGS.lock()
+        .get_string("theme.dir")
+        .unwrap_or(THEME_DIR.to_string());
+    (name, dir)
+}
+
+/// The keys given for each action in the `[keys]` section, as a single key or a list.
+pub fn get_key_bindings() -> HashMap<String, Vec<String>> {
+    let table = SETTINGS.lock()
+        .get_table("keys")
+        .unwrap_or_default();
+    table.into_iter()
+        .map(|(action, keys)| {
+            let keys = match keys.clone().into_array() {
+                Ok(keys) => keys.into_iter().map(|key| key.into_string().unwrap_or_default()).collect(),
+                Err(_) => vec![keys.into_string().unwrap_or_default()],
+            };
+            (action, keys)
+        })
+        .collect()
+}
+
+pub fn is_mouse_enabled() -> bool {
+    SETTINGS.lock()
+        .get_bool("interface.mouse")
+        .unwrap_or(MOUSE_ENABLED)
+}
+
+/// The layout mode, and the panel layouts given in place of the defaults by name.
+pub fn get_layout_settings() -> (String, HashMap<String, String>) {
+    let mode = SETTINGS.lock()
+        .get_string("layout.mode")
+        .unwrap_or(LAYOUT_AUTO.to_string());
+    let descriptions = [LAYOUT_COMPACT, LAYOUT_STANDARD, LAYOUT_WIDE, LAYOUT_DISPLAY].iter()
+        .filter_map(|name| {
+            SETTINGS.lock()
+                .get_string(&format!("layout.{}", name))
+                .ok()
+                .map(|description| (name.to_string(), description))
+        })
+        .collect();
+    (mode, descriptions)
+}
+
+/// Whether to serve the browser front-end, and the address and port to serve it on.
+pub fn get_web_settings() -> Result<(bool, String, u16), Box<dyn Error>> {
+    let enabled = SETTINGS.lock()
+        .get_bool("web.enabled")
+        .unwrap_or(WEB_ENABLED);
+    let bind = SETTINGS.lock()
+        .get_string("web.bind")
+        .unwrap_or(WEB_BIND.to_string());
+    let port = SETTINGS.lock()
+        .get_int("web.port")
+        .unwrap_or(WEB_PORT as i64);
+    let port = u16::try_from(port).map_err(|_| format!("Invalid web port: {}", port))?;
+    Ok((enabled, bind, port))
+}
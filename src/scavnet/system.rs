@@ -1,16 +1,63 @@
 use super::super::SETTINGS;
+use crate::scavnet::director::DirectorInspection;
+use crate::scavnet::history::SessionHistory;
+use crate::scavnet::transmission::library::LibraryRow;
 
 pub struct System {
     log_events: Vec<String>,
+    progress: Option<String>,
+    history: SessionHistory,
+    director_inspection: Option<DirectorInspection>,
+    library_rows: Vec<LibraryRow>,
 }
 
 impl System {
     pub fn new() -> Self {
         System {
             log_events: Vec::new(),
+            progress: None,
+            history: SessionHistory::new(),
+            director_inspection: None,
+            library_rows: Vec::new(),
         }
     }
 
+    /// A line describing a long-running background task, shown above the logs.
+    pub fn set_progress(&mut self, progress: Option<String>) {
+        self.progress = progress;
+    }
+
+    pub fn get_progress(&self) -> Option<String> {
+        self.progress.clone()
+    }
+
+    /// Transmissions heard this session, kept apart from the log so they can be searched.
+    pub fn history(&self) -> &SessionHistory {
+        &self.history
+    }
+
+    pub fn history_mut(&mut self) -> &mut SessionHistory {
+        &mut self.history
+    }
+
+    /// The latest snapshot of the director, shown in the DIRECTOR panel in debug mode.
+    pub fn set_director_inspection(&mut self, inspection: Option<DirectorInspection>) {
+        self.director_inspection = inspection;
+    }
+
+    pub fn get_director_inspection(&self) -> Option<&DirectorInspection> {
+        self.director_inspection.as_ref()
+    }
+
+    /// The transmission library as shown in the library browser.
+    pub fn set_library_rows(&mut self, rows: Vec<LibraryRow>) {
+        self.library_rows = rows;
+    }
+
+    pub fn get_library_rows(&self) -> &[LibraryRow] {
+        &self.library_rows
+    }
+
     pub fn debug_log(&mut self, event: String) {
         let debug = SETTINGS.lock().get_bool("debug").unwrap();
         if debug == true {
@@ -27,7 +74,7 @@ impl System {
         self.log_events.push(event);
     }
 
-    pub fn _get_logs(&self) -> &Vec<String> {
+    pub fn get_logs(&self) -> &Vec<String> {
         &self.log_events
     }
 
@@ -0,0 +1,9 @@
+use tempfile::TempDir;
+
+/// A scratch directory for a test, removed when dropped even if the test fails.
+pub fn test_dir() -> TempDir {
+    tempfile::Builder::new()
+        .prefix("scavnet-test-")
+        .tempdir()
+        .unwrap()
+}
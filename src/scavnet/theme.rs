@@ -0,0 +1,198 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use ratatui::style::Color;
+use serde::Deserialize;
+
+pub const THEME_DEFAULT: &str = "default";
+const THEME_EXTENSION: &str = "yaml";
+
+lazy_static! {
+    static ref THEMES: Mutex<Themes> = Mutex::new(Themes {
+        themes: vec![Theme::default()],
+        current: 0,
+    });
+}
+
+struct Themes {
+    themes: Vec<Theme>,
+    current: usize,
+}
+
+/// The colors every panel, border and chart is drawn with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub background: Color,
+    pub text: Color,
+    pub muted: Color,
+    pub border: Color,
+    pub title: Color,
+    /// Active regions, progress, the scanner cursor and selections.
+    pub highlight: Color,
+    pub good: Color,
+    pub alert: Color,
+    pub spectrum: Color,
+    /// Waterfall intensities, from quiet to loud.
+    pub waterfall: Vec<Color>,
+    /// Alternating colors for networks on the band overview.
+    pub bands: Vec<Color>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: THEME_DEFAULT.to_string(),
+            background: Color::Reset,
+            text: Color::White,
+            muted: Color::Gray,
+            border: Color::White,
+            title: Color::Cyan,
+            highlight: Color::Yellow,
+            good: Color::Green,
+            alert: Color::Red,
+            spectrum: Color::Blue,
+            waterfall: vec![Color::Black, Color::Blue, Color::Cyan, Color::Green, Color::Yellow, Color::Red],
+            bands: vec![Color::Blue, Color::Cyan],
+        }
+    }
+}
+
+/// A theme as written in a file. Colors are names (`yellow`), hex (`#ffb000`) or indexes
+/// (`214`); any left out are taken from the default theme.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    name: Option<String>,
+    background: Option<String>,
+    text: Option<String>,
+    muted: Option<String>,
+    border: Option<String>,
+    title: Option<String>,
+    highlight: Option<String>,
+    good: Option<String>,
+    alert: Option<String>,
+    spectrum: Option<String>,
+    waterfall: Option<Vec<String>>,
+    bands: Option<Vec<String>>,
+}
+
+impl Theme {
+    pub fn from_yaml_str(contents: &str, default_name: &str) -> Result<Self, Box<dyn Error>> {
+        let file: ThemeFile = serde_yaml::from_str(contents)?;
+        let base = Self::default();
+        let color = |value: Option<String>, fallback: Color| -> Result<Color, Box<dyn Error>> {
+            value.map_or(Ok(fallback), |value| parse_color(&value))
+        };
+        let colors = |values: Option<Vec<String>>, fallback: Vec<Color>| -> Result<Vec<Color>, Box<dyn Error>> {
+            match values {
+                Some(values) if !values.is_empty() => values.iter().map(|value| parse_color(value)).collect(),
+                _ => Ok(fallback),
+            }
+        };
+
+        Ok(Self {
+            name: file.name.unwrap_or(default_name.to_string()),
+            background: color(file.background, base.background)?,
+            text: color(file.text, base.text)?,
+            muted: color(file.muted, base.muted)?,
+            border: color(file.border, base.border)?,
+            title: color(file.title, base.title)?,
+            highlight: color(file.highlight, base.highlight)?,
+            good: color(file.good, base.good)?,
+            alert: color(file.alert, base.alert)?,
+            spectrum: color(file.spectrum, base.spectrum)?,
+            waterfall: colors(file.waterfall, base.waterfall)?,
+            bands: colors(file.bands, base.bands)?,
+        })
+    }
+
+    pub fn from_yaml(file_path: &Path) -> Result<Self, Box<dyn Error>> {
+        let default_name = file_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        Self::from_yaml_str(&fs::read_to_string(file_path)?, &default_name)
+    }
+
+    pub fn band_color(&self, index: usize) -> Color {
+        self.bands[index % self.bands.len()]
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color, Box<dyn Error>> {
+    Color::from_str(value).map_err(|_| format!("Invalid color '{}'.", value).into())
+}
+
+/// Loads the theme files in `dir`, alongside the default theme, and switches to the one called
+/// `name`. Returns a message for each file that couldn't be loaded.
+pub fn init_themes(dir: &Path, name: &str) -> Vec<String> {
+    let mut themes = vec![Theme::default()];
+    let mut errors = Vec::new();
+
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect())
+        .unwrap_or_default();
+    paths.sort();
+    for path in paths.iter().filter(|path| path.extension().is_some_and(|extension| extension == THEME_EXTENSION)) {
+        match Theme::from_yaml(path) {
+            Ok(theme) => themes.push(theme),
+            Err(e) => errors.push(format!("Theme {} not loaded: {}", path.display(), e)),
+        }
+    }
+
+    let current = match themes.iter().position(|theme| theme.name == name) {
+        Some(index) => index,
+        None => {
+            errors.push(format!("Theme '{}' not found, using '{}'.", name, THEME_DEFAULT));
+            0
+        }
+    };
+    *THEMES.lock() = Themes { themes, current };
+    errors
+}
+
+pub fn theme() -> Theme {
+    let themes = THEMES.lock();
+    themes.themes[themes.current].clone()
+}
+
+/// Switches to the next loaded theme and returns its name.
+pub fn next_theme() -> String {
+    let mut themes = THEMES.lock();
+    themes.current = (themes.current + 1) % themes.themes.len();
+    themes.themes[themes.current].name.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_from_yaml_str() {
+        let theme = Theme::from_yaml_str("text: \"#ffb000\"\nwaterfall: [black, \"214\"]\n", "amber").unwrap();
+
+        assert_eq!(theme.name, "amber");
+        assert_eq!(theme.text, Color::Rgb(0xff, 0xb0, 0x00));
+        assert_eq!(theme.waterfall, [Color::Black, Color::Indexed(214)]);
+        assert_eq!(theme.title, Theme::default().title);
+    }
+
+    #[test]
+    fn test_init_themes() {
+        let errors = init_themes(Path::new("themes"), "amber");
+
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(theme().name, "amber");
+        assert_eq!(next_theme(), "green-phosphor");
+        assert_eq!(init_themes(Path::new("themes"), "missing").len(), 1);
+        assert_eq!(theme().name, THEME_DEFAULT);
+    }
+
+    #[test]
+    fn test_theme_from_yaml_str_invalid() {
+        assert!(Theme::from_yaml_str("text: notacolor\n", "bad").is_err());
+        assert!(Theme::from_yaml_str("txt: white\n", "bad").is_err());
+    }
+}
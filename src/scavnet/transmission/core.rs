@@ -1,10 +1,15 @@
 use std::fs::File;
 use std::io::{self, BufReader, Read};
+use std::path::Path;
 
+use quanta::Instant;
 use rand::seq::SliceRandom;
 
+use crate::scavnet::bands::SpectrumBands;
 use crate::scavnet::fft::fft_cached_normalized;
 use crate::scavnet::networks::RadioNetworks;
+use crate::scavnet::settings::{get_cache_dir, uses_precomputed_spectrum};
+use crate::scavnet::transmission::sets::CaptionSpec;
 
 #[derive(Clone)]
 pub struct Transmission {
@@ -13,6 +18,11 @@ pub struct Transmission {
     pub items: Vec<TransmissionItem>,
     pub hiss_preroll: f32,
     pub hiss_postroll: f32,
+    pub created_at: Instant,
+    /// Picked by an operator, to be tuned to and played straight away.
+    pub cued: bool,
+    /// The set the conversation was taken from, to build it again.
+    pub set_path: Option<String>,
 }
 
 #[derive(Clone)]
@@ -20,6 +30,7 @@ pub struct TransmissionItem {
     pub id: String,
     pub duration: f32,
     pub caption: String,
+    pub captions: Vec<Caption>,
     pub file_path: String,
     pub sleep_after: f32,
     pub file_bytes: Vec<u8>,
@@ -27,22 +38,35 @@ pub struct TransmissionItem {
 }
 
 impl TransmissionItem {
-    pub fn new(id: String, caption: String, file_path: String, sleep_after: f32) -> Self {
+    pub fn new(id: String, captions: &[CaptionSpec], file_path: String, sleep_after: f32) -> Self {
         let reader: hound::WavReader<io::BufReader<File>> = hound::WavReader::open(file_path.clone()).unwrap();
         let duration = reader.duration();
-        let sample_rate = reader.spec().sample_rate as f32;
-        let duration_seconds = duration as f32 / sample_rate;
-        let fft_data = fft_cached_normalized(file_path.as_str()).unwrap();
+        let sample_rate = reader.spec().sample_rate;
+        let duration_seconds = duration as f32 / sample_rate as f32;
+        let fft_data = match uses_precomputed_spectrum().then(SpectrumBands::from_settings) {
+            Some(Ok(bands)) => {
+                fft_cached_normalized(file_path.as_str(), Path::new(&get_cache_dir()))
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|frame| bands.map(frame, sample_rate))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
 
         let file = File::open(file_path.clone()).unwrap();
         let mut buf_reader = BufReader::new(file);
         let mut file_bytes = Vec::new();
         buf_reader.read_to_end(&mut file_bytes).unwrap();
 
+        let captions = resolve_captions(captions, duration_seconds);
+        let caption = captions.iter().map(|caption| caption.text.as_str()).collect::<Vec<_>>().join("\n");
+
         Self {
             id,
             duration: duration_seconds,
             caption,
+            captions,
             file_path,
             sleep_after,
             file_bytes,
@@ -50,6 +74,40 @@ impl TransmissionItem {
         }
     }
 
+    /// The caption showing `position` seconds into the item, if any.
+    pub fn caption_at(&self, position: f32) -> Option<&str> {
+        self.captions.iter()
+            .rev()
+            .find(|caption| caption.at <= position)
+            .map(|caption| caption.text.as_str())
+    }
+}
+
+/// A caption segment, shown from `at` seconds into its item until the next one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Caption {
+    pub at: f32,
+    pub text: String,
+}
+
+/// Places captions within an item `duration` seconds long. Captions without a time are spread
+/// evenly across the item by their position in the list.
+pub fn resolve_captions(specs: &[CaptionSpec], duration: f32) -> Vec<Caption> {
+    let mut captions: Vec<Caption> = specs.iter()
+        .enumerate()
+        .map(|(index, spec)| match spec {
+            CaptionSpec::Text(text) => Caption {
+                at: duration * index as f32 / specs.len() as f32,
+                text: text.trim_end().to_string(),
+            },
+            CaptionSpec::Timed { at, text } => Caption {
+                at: *at,
+                text: text.trim_end().to_string(),
+            },
+        })
+        .collect();
+    captions.sort_by(|a, b| a.at.total_cmp(&b.at));
+    captions
 }
 
 impl Transmission {
@@ -60,6 +118,9 @@ impl Transmission {
             items: Vec::new(),
             hiss_preroll: 0.0,
             hiss_postroll: 0.0,
+            created_at: Instant::now(),
+            cued: false,
+            set_path: None,
         }
     }
 
@@ -73,3 +134,25 @@ impl Transmission {
         self.items.push(item);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_captions() {
+        let specs = vec![
+            CaptionSpec::Text("First".to_string()),
+            CaptionSpec::Text("Second\nline two\n".to_string()),
+            CaptionSpec::Timed { at: 1.0, text: "Early".to_string() },
+        ];
+
+        let captions = resolve_captions(&specs, 6.0);
+
+        assert_eq!(captions, [
+            Caption { at: 0.0, text: "First".to_string() },
+            Caption { at: 1.0, text: "Early".to_string() },
+            Caption { at: 2.0, text: "Second\nline two".to_string() },
+        ]);
+    }
+}
@@ -5,8 +5,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::scavnet::{networks::RadioNetworks, transmission::{core::Transmission, sets::{Conversation, ConversationTransmissionItemSpec}}};
 use crate::scavnet::transmission::core::TransmissionItem;
+use crate::scavnet::settings::transmission_data_dir;
 use crate::scavnet::transmission::interfaces::core::init_transmission;
 
+#[allow(dead_code)]
 pub struct TransmissionLocalFileSystem {
     pub path: String,
 }
@@ -22,9 +24,7 @@ impl TransmissionLocalFileSystem {
 
         let mut items_iter = conversation.items.iter().peekable();
         while let Some(item) = items_iter.next() {
-            let data: LocalFileSystemData = serde_yaml::from_value(item.data.clone())?;
-            let file_path = data.file.clone();
-            let file_path_string = format!("data/transmissions/{}", file_path);
+            let file_path_string = Self::item_file_path(item)?;
 
             let mut sleep_after: f32 = 0.0;
             if items_iter.peek().is_some() {
@@ -33,7 +33,7 @@ impl TransmissionLocalFileSystem {
 
             let transmission_item = TransmissionItem::new(
                 item.id.clone(),
-                item.captions[0].clone(),
+                &item.captions,
                 file_path_string.clone(),
                 sleep_after,
             );
@@ -43,6 +43,12 @@ impl TransmissionLocalFileSystem {
         Ok(transmission)
     }
 
+    /// Where the audio for `item` is, under the transmissions data directory.
+    pub fn item_file_path(item: &ConversationTransmissionItemSpec) -> Result<String, Box<dyn Error>> {
+        let data: LocalFileSystemData = serde_yaml::from_value(item.data.clone())?;
+        Ok(format!("{}/{}", transmission_data_dir(), data.file))
+    }
+
     fn get_delay(conversation: ConversationTransmissionItemSpec) -> f32 {
         let delaymin = conversation.delay_after_min as f32;
         let delaymax = conversation.delay_after_max as f32;
@@ -85,7 +85,7 @@ impl TransmissionOpenAI {
     
                 let transmission_item = TransmissionItem::new(
                     item.id.clone(),
-                    item.captions[0].clone(),
+                    &item.captions,
                     file_path,
                     sleep_after,
                 );
@@ -8,8 +8,8 @@ use rand::Rng;
 use serde_yaml::from_reader;
 use serde::Deserialize;
 
-use crate::scavnet::fft::write_fft_all_wav_files_in_dir;
 use crate::scavnet::settings::get_data_dir;
+use crate::scavnet::transmission::sets::TransmissionSet;
 
 #[derive(Debug, Deserialize)]
 struct Set {
@@ -46,7 +46,9 @@ impl TransmissionSetNode {
         self.branches.insert(label, (weight, Box::new(child)));
     }
 
-    pub fn traverse(&self, rng: &mut impl Rng) -> &Self {
+    /// Picks a leaf by weight, recording each branch taken in `path` as its label, weight and
+    /// chance of being picked among its siblings.
+    pub fn traverse(&self, rng: &mut impl Rng, path: &mut Vec<(String, f64, f64)>) -> &Self {
         let total_weight: f64 = self.branches.values().map(|(weight, _)| weight).sum();
         if total_weight == 0.0 {
             return self;
@@ -55,10 +57,14 @@ impl TransmissionSetNode {
         let mut cumulative_weight = 0.0;
         let random_value: f64 = rng.gen::<f64>() * total_weight;
 
-        for (_label, (weight, child)) in &self.branches {
+        // In a fixed order, so a seeded rng picks the same branches every run.
+        let mut branches: Vec<_> = self.branches.iter().collect();
+        branches.sort_by(|a, b| a.0.cmp(b.0));
+        for (label, (weight, child)) in branches {
             cumulative_weight += weight;
             if random_value < cumulative_weight {
-                return child.traverse(rng);
+                path.push((label.clone(), *weight, weight / total_weight));
+                return child.traverse(rng, path);
             }
         }
 
@@ -98,6 +104,23 @@ impl TransmissionSetNode {
 
 }
 
+#[derive(Clone, Debug)]
+pub enum LibraryRowKind {
+    Set,
+    Conversation { set_path: String, id: String, played: bool },
+    /// A set whose file couldn't be read, and why.
+    Error(String),
+}
+
+/// A line of the library browser: a set or conversation at `depth` in the tree.
+#[derive(Clone, Debug)]
+pub struct LibraryRow {
+    pub depth: usize,
+    pub label: String,
+    pub weight: f64,
+    pub kind: LibraryRowKind,
+}
+
 #[derive(Clone)]
 pub struct TransmissionLibrary {
     library: TransmissionSetNode,
@@ -116,8 +139,53 @@ impl TransmissionLibrary {
         }
     }
 
-    pub fn choose(&self, rng: &mut impl Rng) -> &TransmissionSetNode {
-        self.library.traverse(rng)
+    pub fn choose(&self, rng: &mut impl Rng, path: &mut Vec<(String, f64, f64)>) -> &TransmissionSetNode {
+        self.library.traverse(rng, path)
+    }
+
+    /// The library tree, sets sorted by name, with the conversations of each leaf set read
+    /// from its file along with whether they have been played.
+    pub fn rows(&self) -> Vec<LibraryRow> {
+        let mut rows = Vec::new();
+        Self::add_rows(&self.library, 0, &mut rows);
+        rows
+    }
+
+    fn add_rows(node: &TransmissionSetNode, depth: usize, rows: &mut Vec<LibraryRow>) {
+        let mut branches: Vec<_> = node.branches.iter().collect();
+        branches.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (label, (weight, child)) in branches {
+            rows.push(LibraryRow { depth, label: label.clone(), weight: *weight, kind: LibraryRowKind::Set });
+            if !child.branches.is_empty() {
+                Self::add_rows(child, depth + 1, rows);
+                continue;
+            }
+
+            let set_path = child.data.clone().unwrap_or_default();
+            match TransmissionSet::from_yaml(&set_path) {
+                Ok(set) => {
+                    for conversation in &set.conversations {
+                        rows.push(LibraryRow {
+                            depth: depth + 1,
+                            label: conversation.id.clone(),
+                            weight: conversation.weight as f64,
+                            kind: LibraryRowKind::Conversation {
+                                set_path: set_path.clone(),
+                                id: conversation.id.clone(),
+                                played: set.is_played(&conversation.id),
+                            },
+                        });
+                    }
+                }
+                Err(e) => rows.push(LibraryRow {
+                    depth: depth + 1,
+                    label: set_path,
+                    weight: 0.0,
+                    kind: LibraryRowKind::Error(e.to_string()),
+                }),
+            }
+        }
     }
 
     pub fn build(file_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
@@ -128,13 +196,5 @@ impl TransmissionLibrary {
 
 pub async fn build_transmission_library(file_path: &str) -> Result<TransmissionLibrary, Box<dyn Error>> {
     let transmission_path = std::path::Path::new(&file_path);
-    precompute_library_ffts();
     TransmissionLibrary::build(transmission_path)
 }
-
-fn precompute_library_ffts() {
-    let data_dir = get_data_dir();
-    let transmission_path_str = format!("{}/transmissions", data_dir);
-    let transmission_path = std::path::Path::new(&transmission_path_str);
-    write_fft_all_wav_files_in_dir(transmission_path);
-}
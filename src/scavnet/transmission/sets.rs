@@ -4,14 +4,24 @@ use std::fs::File;
 use std::io::BufReader;
 
 use rand::seq::SliceRandom;
+use rand::Rng;
 use serde_yaml::from_reader;
 use serde_yaml::Value;
 use serde::{Deserialize, Serialize};
 
+/// A caption as written in a conversation file: plain text, or text shown from `at` seconds
+/// into the item.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CaptionSpec {
+    Text(String),
+    Timed { at: f32, text: String },
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ConversationTransmissionItemSpec {
     pub id: String,
-    pub captions: Vec<String>,
+    pub captions: Vec<CaptionSpec>,
     pub duration: f32,
     pub delay_after_min: u64,
     pub delay_after_max: u64,
@@ -67,7 +77,17 @@ impl TransmissionSet {
         Ok(transmission_set)
     }
 
-    pub fn get_conversation(&mut self) -> Option<&Conversation> {
+    /// Takes the next conversation, in the set's playback order and replay mode, and marks it
+    /// played.
+    pub fn get_conversation(&mut self, rng: &mut impl Rng) -> Option<&Conversation> {
+        let id = self.next_conversation(rng)?.id.clone();
+        self.write_state();
+        self.conversations.iter().find(|conv| conv.id == id)
+    }
+
+    /// Like `get_conversation`, but only remembers the conversation was played until the set is
+    /// dropped.
+    pub fn next_conversation(&mut self, rng: &mut impl Rng) -> Option<&Conversation> {
         let next_conversation = match self.playback.as_str() {
             "sequence" => {
                 // Find the first conversation that hasn't been played yet.
@@ -82,7 +102,6 @@ impl TransmissionSet {
                 }
             }
             "random" => {
-                let mut rng = rand::thread_rng();
                 let replay_mode = self.replay_mode.as_str();
 
                 let mut conversation_pool: Vec<_> = if replay_mode == "exhaust" {
@@ -100,7 +119,7 @@ impl TransmissionSet {
                 }
 
                 conversation_pool
-                    .choose_weighted(&mut rng, |conv| conv.weight)
+                    .choose_weighted(rng, |conv| conv.weight)
                     .ok()
                     .map(|conv| *conv)
             }
@@ -109,14 +128,72 @@ impl TransmissionSet {
 
         if let Some(conversation) = next_conversation {
             self.conversations_state.push(conversation.id.clone());
-            // Write the updated state to the sidecar file
-            let sidecar_path = format!("{}.state", self.file_path);
-            if let Ok(sidecar_contents) = serde_yaml::to_string(&self.conversations_state) {
-                let _ = fs::write(&sidecar_path, sidecar_contents);
-            }
             Some(conversation)
         } else {
             None
         }
     }
+
+    /// Takes the conversation `id`, regardless of playback order, and marks it played.
+    pub fn take_conversation(&mut self, id: &str) -> Option<&Conversation> {
+        let conversation = self.conversations.iter().find(|conv| conv.id == id)?;
+        if !self.conversations_state.contains(&conversation.id) {
+            self.conversations_state.push(conversation.id.clone());
+        }
+        self.write_state();
+        Some(conversation)
+    }
+
+    pub fn is_played(&self, id: &str) -> bool {
+        self.conversations_state.iter().any(|played| played == id)
+    }
+
+    // Write the updated state to the sidecar file
+    fn write_state(&self) {
+        let sidecar_path = format!("{}.state", self.file_path);
+        if let Ok(sidecar_contents) = serde_yaml::to_string(&self.conversations_state) {
+            let _ = fs::write(&sidecar_path, sidecar_contents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    fn test_set(playback: &str) -> TransmissionSet {
+        let conversation = |id: &str| format!(
+            "  - {{ id: {}, description: '', weight: 1, interface: localfilesystem, transmissions: {{ random_frequency: true, frequency: 0, items: [] }} }}\n",
+            id,
+        );
+        let yaml = format!(
+            "name: test\ndescription: ''\nplayback: {}\nreplay_mode: exhaust\nconversations:\n{}{}{}",
+            playback, conversation("a"), conversation("b"), conversation("c"),
+        );
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    fn picks(set: &mut TransmissionSet, seed: u64, count: usize) -> Vec<String> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count).map(|_| set.next_conversation(&mut rng).unwrap().id.clone()).collect()
+    }
+
+    #[test]
+    fn test_next_conversation_in_sequence() {
+        assert_eq!(picks(&mut test_set("sequence"), 1, 4), ["a", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_next_conversation_exhausts_before_repeating() {
+        let picked = picks(&mut test_set("random"), 1, 6);
+
+        let mut first_round = picked[..3].to_vec();
+        first_round.sort();
+        assert_eq!(first_round, ["a", "b", "c"]);
+        // The same seed picks the same conversations.
+        assert_eq!(picks(&mut test_set("random"), 1, 6), picked);
+    }
 }
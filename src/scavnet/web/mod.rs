@@ -0,0 +1,217 @@
+pub mod snapshot;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use quanta::Instant;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::{Message, Role};
+
+use crate::scavnet::scanner::Scanner;
+use crate::scavnet::system::System;
+use snapshot::ScannerSnapshot;
+
+const INDEX_HTML: &str = include_str!("index.html");
+const MAX_REQUEST_HEAD: usize = 8192;
+// Connections that don't finish sending their request in time are dropped.
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(5);
+// Snapshots are sent at most this often, however fast the TUI redraws.
+const PUBLISH_INTERVAL: Duration = Duration::from_millis(50);
+
+type ConnectionResult = Result<(), Box<dyn Error + Send + Sync>>;
+
+/// Serves the browser front-end and streams snapshots of the scanner to it over WebSocket.
+pub struct WebServer {
+    address: String,
+    snapshots: watch::Sender<String>,
+    last_published: Instant,
+}
+
+impl WebServer {
+    pub async fn start(address: &str) -> Result<Self, Box<dyn Error>> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?.to_string();
+        let (snapshots, _) = watch::channel(String::new());
+
+        let connection_snapshots = snapshots.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let snapshots = connection_snapshots.subscribe();
+                tokio::spawn(async move {
+                    let _ = handle_connection(stream, snapshots).await;
+                });
+            }
+        });
+
+        Ok(Self {
+            address,
+            snapshots,
+            last_published: Instant::now(),
+        })
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Sends what the TUI is showing to every connected page.
+    pub fn publish(&mut self, scanner: &Scanner, system: &System) {
+        if self.snapshots.receiver_count() == 0 || self.last_published.elapsed() < PUBLISH_INTERVAL {
+            return;
+        }
+        if let Ok(json) = serde_json::to_string(&ScannerSnapshot::new(scanner, system)) {
+            self.snapshots.send_replace(json);
+        }
+        self.last_published = Instant::now();
+    }
+}
+
+/// The path and lowercased headers of an HTTP GET request head.
+fn parse_request(head: &str) -> Option<(String, HashMap<String, String>)> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    if request_line.next()? != "GET" {
+        return None;
+    }
+    let path = request_line.next()?.to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+    Some((path, headers))
+}
+
+/// Whether a request comes from a page this server served, or from outside a browser. Pages on
+/// other sites could otherwise read the scanner through a visitor's browser.
+fn is_same_origin(headers: &HashMap<String, String>) -> bool {
+    let Some(origin) = headers.get("origin") else {
+        return true;
+    };
+    let origin_host = origin.split_once("://").map_or(origin.as_str(), |(_, host)| host);
+    headers.get("host").is_some_and(|host| host.eq_ignore_ascii_case(origin_host))
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> Result<Option<Vec<u8>>, Box<dyn Error + Send + Sync>> {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 || head.len() + read > MAX_REQUEST_HEAD {
+            return Ok(None);
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    Ok(Some(head))
+}
+
+async fn handle_connection(mut stream: TcpStream, snapshots: watch::Receiver<String>) -> ConnectionResult {
+    let Ok(Ok(Some(head))) = tokio::time::timeout(REQUEST_HEAD_TIMEOUT, read_request_head(&mut stream)).await else {
+        return Ok(());
+    };
+
+    let Some((path, headers)) = parse_request(&String::from_utf8_lossy(&head)) else {
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", "Method not allowed.").await;
+    };
+    match path.as_str() {
+        "/" | "/index.html" => respond(&mut stream, "200 OK", "text/html; charset=utf-8", INDEX_HTML).await,
+        "/ws" if !is_same_origin(&headers) => respond(&mut stream, "403 Forbidden", "text/plain", "Cross-origin requests are not allowed.").await,
+        "/ws" => match headers.get("sec-websocket-key") {
+            Some(key) if headers.get("upgrade").is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket")) => {
+                let response = format!(
+                    "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                    derive_accept_key(key.as_bytes())
+                );
+                stream.write_all(response.as_bytes()).await?;
+                stream_snapshots(stream, snapshots).await
+            }
+            _ => respond(&mut stream, "400 Bad Request", "text/plain", "Expected a WebSocket upgrade.").await,
+        },
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "Not found.").await,
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> ConnectionResult {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, content_type, body.len(), body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+// Sends each new snapshot until the page goes away. Pings and closes from the page are answered
+// as its messages are read.
+async fn stream_snapshots(stream: TcpStream, mut snapshots: watch::Receiver<String>) -> ConnectionResult {
+    let socket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+    let (mut writer, mut reader) = socket.split();
+
+    loop {
+        tokio::select! {
+            changed = snapshots.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let json = snapshots.borrow_and_update().clone();
+                writer.send(Message::text(json)).await?;
+            }
+            message = reader.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+    #[test]
+    fn test_parse_request() {
+        let (path, headers) = parse_request("GET /ws HTTP/1.1\r\nHost: localhost\r\nSec-WebSocket-Key: abc==\r\n\r\n").unwrap();
+
+        assert_eq!(path, "/ws");
+        assert_eq!(headers.get("sec-websocket-key").unwrap(), "abc==");
+        assert!(parse_request("POST / HTTP/1.1\r\n\r\n").is_none());
+    }
+
+    #[test]
+    fn test_is_same_origin() {
+        let headers = |origin: Option<&str>| {
+            let mut headers = HashMap::from([("host".to_string(), "localhost:8080".to_string())]);
+            if let Some(origin) = origin {
+                headers.insert("origin".to_string(), origin.to_string());
+            }
+            headers
+        };
+
+        assert!(is_same_origin(&headers(None)));
+        assert!(is_same_origin(&headers(Some("http://localhost:8080"))));
+        assert!(!is_same_origin(&headers(Some("https://example.com"))));
+        assert!(!is_same_origin(&headers(Some("http://localhost:9090"))));
+    }
+
+    #[tokio::test]
+    async fn test_stream_snapshots() {
+        let server = WebServer::start("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/ws", server.address());
+        let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str()).await.unwrap();
+
+        server.snapshots.send_replace("{}".to_string());
+        let message = socket.next().await.unwrap().unwrap();
+        assert_eq!(message, Message::text("{}"));
+
+        let mut foreign = url.into_client_request().unwrap();
+        foreign.headers_mut().insert("Origin", "https://example.com".parse().unwrap());
+        assert!(tokio_tungstenite::connect_async(foreign).await.is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+
+use crate::scavnet::bands::frequency_label;
+use crate::scavnet::scanner::Scanner;
+use crate::scavnet::system::System;
+
+// Log lines sent to the page.
+const SNAPSHOT_LOG_LINES: usize = 20;
+
+/// A network's share of the band overview, as start and end fractions.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BandSegment {
+    pub name: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// What the TUI shows, as sent to the browser front-end.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ScannerSnapshot {
+    pub frequency: String,
+    pub network: String,
+    pub status: String,
+    pub caption: Option<String>,
+    /// The latest spectrum frame, one value per band.
+    pub spectrum: Vec<f32>,
+    /// Labels for the lowest and highest spectrum bands.
+    pub spectrum_range: (String, String),
+    pub band: Vec<BandSegment>,
+    /// Where the scanner is on the band, from 0 to 1.
+    pub band_position: Option<f64>,
+    pub log: Vec<String>,
+}
+
+impl ScannerSnapshot {
+    pub fn new(scanner: &Scanner, system: &System) -> Self {
+        let networks = scanner.networks();
+        let bands = scanner.spectrum_bands();
+        Self {
+            frequency: scanner.cur_freq_display(),
+            network: scanner.cur_network_name().to_uppercase(),
+            status: scanner.status(),
+            caption: scanner.caption(),
+            spectrum: scanner.get_fft_data(),
+            spectrum_range: (frequency_label(bands.frequency_at(0)), frequency_label(bands.frequency_at(bands.count()))),
+            band: networks.band_segments()
+                .into_iter()
+                .map(|(name, start, end)| BandSegment { name: name.to_uppercase(), start, end })
+                .collect(),
+            band_position: networks.band_position(scanner.cur_freq() as u64),
+            log: system.get_last_x_logs(SNAPSHOT_LOG_LINES),
+        }
+    }
+}